@@ -21,6 +21,34 @@ function asynccall(func::Function, wakeptr::Ptr{Cvoid}, args...)::Task
     Base.Threads.@spawn runasync(func, wakeptr, args...)
 end
 
+# `flag` is a reference to the flag of an `Interrupt` that is owned by this call, it's released
+# by calling `release` when the call returns.
+function interruptiblecall(func, flag::Ptr{UInt8}, release::Ptr{Cvoid}, timeout::Float64, args...)
+    try
+        task = @async func(args...)
+        start = time()
+
+        timer = Timer(0.0; interval = 0.01) do timer
+            if istaskdone(task)
+                close(timer)
+            elseif unsafe_load(flag) != 0 || (timeout >= 0 && time() - start >= timeout)
+                close(timer)
+                schedule(task, InterruptException(); error = true)
+            end
+        end
+
+        try
+            fetch(task)
+        catch err
+            err isa TaskFailedException ? throw(err.task.exception) : rethrow()
+        finally
+            close(timer)
+        end
+    finally
+        ccall(release, Cvoid, (Ptr{UInt8},), flag)
+    end
+end
+
 function tracingcall(func::Function)::Function
     function wrapper(args...)
         try
@@ -49,4 +77,68 @@ function attachstacktrace(func::Function)::Function
 
     wrapper
 end
+
+struct Redirection
+    pipe::Pipe
+    reader::Task
+    previous::IO
+end
+
+const redirections = Dict{Symbol,Vector{Redirection}}(:stdout => [], :stderr => [])
+
+function redirect(stream::Symbol, writefn::Ptr{Cvoid}, sink::Ptr{Cvoid})::Nothing
+    pipe = Pipe()
+    Base.link_pipe!(pipe; reader_supports_async = true, writer_supports_async = true)
+    previous = stream === :stdout ? stdout : stderr
+
+    reader = @async while !eof(pipe)
+        bytes = readavailable(pipe)
+        ccall(writefn, Cvoid, (Ptr{Cvoid}, Ptr{UInt8}, Csize_t), sink, bytes, length(bytes))
+    end
+
+    stream === :stdout ? redirect_stdout(pipe.in) : redirect_stderr(pipe.in)
+    push!(redirections[stream], Redirection(pipe, reader, previous))
+    nothing
+end
+
+function restore(stream::Symbol)::Bool
+    isempty(redirections[stream]) && return false
+    redirection = pop!(redirections[stream])
+    stream === :stdout ? redirect_stdout(redirection.previous) : redirect_stderr(redirection.previous)
+    close(redirection.pipe.in)
+    wait(redirection.reader)
+    true
+end
+
+struct RustLogger <: Base.CoreLogging.AbstractLogger
+    logfn::Ptr{Cvoid}
+    minlevel::Base.CoreLogging.LogLevel
+end
+
+Base.CoreLogging.min_enabled_level(logger::RustLogger) = logger.minlevel
+Base.CoreLogging.shouldlog(::RustLogger, args...) = true
+Base.CoreLogging.catch_exceptions(::RustLogger) = true
+
+function Base.CoreLogging.handle_message(logger::RustLogger, level, message, _module, group, id, file, line; kwargs...)
+    keys = String[string(key) for (key, _) in kwargs]
+    values = String[string(value) for (_, value) in kwargs]
+    file = file === nothing ? "" : string(file)
+    line = line === nothing ? 0 : Int64(line)
+
+    GC.@preserve keys values begin
+        ccall(logger.logfn, Cvoid,
+            (Int32, Cstring, Cstring, Cstring, Int64, Ptr{Ptr{UInt8}}, Ptr{Ptr{UInt8}}, Csize_t),
+            Int32(convert(Base.CoreLogging.LogLevel, level).level), string(message),
+            string(_module), file, line, pointer.(keys), pointer.(values), length(keys))
+    end
+end
+
+function installlogger(logfn::Ptr{Cvoid}, minlevel::Int32)::Nothing
+    Base.CoreLogging.global_logger(RustLogger(logfn, Base.CoreLogging.LogLevel(minlevel)))
+    nothing
+end
+
+# The values rooted by `Persistent`s. Every handle owns a slot, jlrs stores values in it and
+# resets it to `nothing` through the C API so the exact object that is rooted is stored.
+const persistent = Any[]
 end
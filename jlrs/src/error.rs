@@ -1,6 +1,7 @@
 //! Everything related to errors.
 
 use crate::value::array::Dimensions;
+use crate::version::JuliaVersion;
 use std::error::Error;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
@@ -29,7 +30,17 @@ pub enum JlrsError {
     NotAString,
     NotUnicode,
     NotAnSVec,
+    NotANamedTuple,
     NotAnSSAValue,
+    NotACodeInfo,
+    NotAGotoNode,
+    NotAPiNode,
+    NotAPhiNode,
+    NotAPhiCNode,
+    NotAnUpsilonNode,
+    NotAQuoteNode,
+    NotANewVarNode,
+    NotALineNumberNode,
     NotATypeName,
     NotATypeVar,
     NotAUnion,
@@ -37,6 +48,7 @@ pub enum JlrsError {
     FunctionNotFound(String),
     IncludeNotFound(String),
     IncludeError(String, String),
+    LoadError(String, String),
     NoSuchField(String),
     InvalidArrayType,
     InvalidCharacter,
@@ -53,6 +65,12 @@ pub enum JlrsError {
     InvalidIndex(Dimensions, Dimensions),
     Immutable,
     NotSubtype,
+    NotConcrete(String),
+    WrongFieldCount(usize, usize),
+    Interrupted,
+    IncompatibleVersion(JuliaVersion),
+    InvalidOptimizationLevel(u8),
+    AmbiguousMethod,
 }
 
 pub fn exception<T>(exc: String) -> JlrsResult<T> {
@@ -102,6 +120,11 @@ impl Display for JlrsError {
                 "The file {} could not be included successfully. Exception type: {}",
                 inc, err_type
             ),
+            JlrsError::LoadError(package, err_type) => write!(
+                formatter,
+                "The package {} could not be loaded successfully. Exception type: {}",
+                package, err_type
+            ),
             JlrsError::InvalidArrayType => write!(formatter, "Invalid array type"),
             JlrsError::InvalidCharacter => write!(formatter, "Invalid character"),
             JlrsError::NullFrame => write!(
@@ -116,7 +139,17 @@ impl Display for JlrsError {
             }
             JlrsError::NotAMethTable => write!(formatter, "This is not a method table"),
             JlrsError::NotAnSVec => write!(formatter, "This is not a simple vector"),
+            JlrsError::NotANamedTuple => write!(formatter, "This is not a named tuple"),
             JlrsError::NotAnSSAValue => write!(formatter, "This is not an SSA value"),
+            JlrsError::NotACodeInfo => write!(formatter, "This is not code info"),
+            JlrsError::NotAGotoNode => write!(formatter, "This is not a goto node"),
+            JlrsError::NotAPiNode => write!(formatter, "This is not a pi node"),
+            JlrsError::NotAPhiNode => write!(formatter, "This is not a phi node"),
+            JlrsError::NotAPhiCNode => write!(formatter, "This is not a phic node"),
+            JlrsError::NotAnUpsilonNode => write!(formatter, "This is not an upsilon node"),
+            JlrsError::NotAQuoteNode => write!(formatter, "This is not a quote node"),
+            JlrsError::NotANewVarNode => write!(formatter, "This is not a newvar node"),
+            JlrsError::NotALineNumberNode => write!(formatter, "This is not a line number node"),
             JlrsError::NotATypeName => write!(formatter, "This is not a typename"),
             JlrsError::NotATypeVar => write!(formatter, "This is not a type var"),
             JlrsError::NotAUnion => write!(formatter, "This is not a union"),
@@ -126,9 +159,34 @@ impl Display for JlrsError {
             JlrsError::NotACodeInstance => write!(formatter, "This is not a code instance"),
             JlrsError::NotAWeakRef => write!(formatter, "This is not a weak ref"),
             JlrsError::Immutable => write!(formatter, "This value is immutable"),
+            JlrsError::Interrupted => write!(formatter, "The function call was interrupted"),
+            JlrsError::IncompatibleVersion(version) => write!(
+                formatter,
+                "Julia {} is not supported, jlrs requires Julia {}.{}",
+                version,
+                JuliaVersion::SUPPORTED.0,
+                JuliaVersion::SUPPORTED.1
+            ),
+            JlrsError::InvalidOptimizationLevel(level) => write!(
+                formatter,
+                "The optimization level must be between 0 and 3, found {}",
+                level
+            ),
+            JlrsError::AmbiguousMethod => {
+                write!(
+                    formatter,
+                    "The method is ambiguous for the given argument types"
+                )
+            }
             JlrsError::NotSubtype => {
                 write!(formatter, "Value type is not a subtype of the field type")
             }
+            JlrsError::NotConcrete(ty) => write!(formatter, "{} is not a concrete type", ty),
+            JlrsError::WrongFieldCount(expected, found) => write!(
+                formatter,
+                "Expected a value for each of the {} fields, found {}",
+                expected, found
+            ),
             JlrsError::NotATypeMapEntry => write!(formatter, "This is not a typemap entry"),
             JlrsError::NotATypeMapLevel => write!(formatter, "This is not a typemap level"),
             JlrsError::NotAnExpr => write!(formatter, "This is not an expr"),
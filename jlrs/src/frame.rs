@@ -22,7 +22,13 @@
 //! Frames have a lifetime, `'frame`. This lifetime ensures that a [`Value`] can only be used as
 //! long as the frame that protects it has not been dropped.
 //!
-//! Most functionality that frames implement is defined by the [`Frame`] trait.
+//! Most functionality that frames implement is defined by the [`Frame`] trait. Slots of a frame
+//! can be reserved for later use, either as an [`Output`] that is used once or as a [`Slot`]
+//! that can be reused.
+//!
+//! The frames that are currently active can be inspected with [`Frame::frame_info`] and
+//! [`Frame::frame_chain`], which return a [`FrameInfo`] for each frame. This can be used to
+//! check how many slots a piece of code uses or which values are rooted by a frame.
 //!
 //! [`StaticFrame`]: struct.StaticFrame.html
 //! [`DynamicFrame`]: struct.DynamicFrame.html
@@ -31,6 +37,11 @@
 //! [`Value`]: ../value/struct.Value.html
 //! [`Value::call_async`]: ../value/struct.Value.html#method.call_async
 //! [`Frame`]: ../traits/trait.Frame.html
+//! [`Frame::frame_info`]: ../traits/trait.Frame.html#method.frame_info
+//! [`Frame::frame_chain`]: ../traits/trait.Frame.html#method.frame_chain
+//! [`FrameInfo`]: struct.FrameInfo.html
+//! [`Output`]: struct.Output.html
+//! [`Slot`]: struct.Slot.html
 //! [`JuliaTask`]: ../traits/multitask/trait.JuliaTask.html
 
 use crate::error::JlrsResult;
@@ -38,11 +49,76 @@ use crate::error::JlrsResult;
 use crate::mode::Async;
 use crate::mode::Mode;
 use crate::stack::{Dynamic, StackView, Static};
+use crate::traits::private::Internal;
+use crate::traits::Frame;
+use crate::value::Value;
 use crate::CCall;
+use std::ffi::c_void;
 use std::marker::PhantomData;
 
 #[derive(Copy, Clone, Default)]
-pub struct FrameIdx(pub(crate) usize);
+pub struct FrameIdx {
+    // The segment of the stack that contains the frame.
+    pub(crate) segment: usize,
+    // The offset of the first slot of the frame in that segment.
+    pub(crate) offset: usize,
+    // The segment that was in use when the frame was pushed.
+    pub(crate) prev: usize,
+}
+
+/// The kind of a frame, returned as part of its [`FrameInfo`].
+///
+/// [`FrameInfo`]: struct.FrameInfo.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameKind {
+    /// A [`StaticFrame`].
+    ///
+    /// [`StaticFrame`]: struct.StaticFrame.html
+    Static,
+    /// A [`DynamicFrame`].
+    ///
+    /// [`DynamicFrame`]: struct.DynamicFrame.html
+    Dynamic,
+    /// An [`AsyncFrame`].
+    ///
+    /// [`AsyncFrame`]: struct.AsyncFrame.html
+    Async,
+    /// A [`NullFrame`].
+    ///
+    /// [`NullFrame`]: struct.NullFrame.html
+    Null,
+}
+
+/// Information about a frame, returned by [`Frame::frame_info`] and [`Frame::frame_chain`].
+///
+/// [`Frame::frame_info`]: ../traits/trait.Frame.html#method.frame_info
+/// [`Frame::frame_chain`]: ../traits/trait.Frame.html#method.frame_chain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// The kind of frame.
+    pub kind: FrameKind,
+    /// The number of slots a static frame has been created with, `None` for other frames.
+    pub capacity: Option<usize>,
+    /// The number of slots that are in use, including outputs.
+    pub len: usize,
+    /// The number of slots that have been reserved as an output or slot.
+    pub outputs: usize,
+    /// The type of the value in each slot that is in use, `None` if the slot is empty. This is
+    /// only available if it has been requested.
+    pub root_types: Option<Vec<Option<String>>>,
+}
+
+impl FrameInfo {
+    pub(crate) fn null() -> Self {
+        FrameInfo {
+            kind: FrameKind::Null,
+            capacity: None,
+            len: 0,
+            outputs: 0,
+            root_types: None,
+        }
+    }
+}
 
 /// A `StaticFrame` is a frame that has a definite number of slots on the GC stack. With some
 /// exceptions, creating new `Value`s and calling them require one slot each. Rather than using
@@ -141,10 +217,11 @@ impl<'frame, M: Mode> DynamicFrame<'frame, M> {
     pub(crate) unsafe fn nested_frame<'nested>(
         &'nested mut self,
     ) -> JlrsResult<DynamicFrame<'nested, M>> {
-        let idx = self.memory.new_frame()?;
+        let mut memory = self.memory.nest_dynamic();
+        let idx = memory.new_frame()?;
         Ok(DynamicFrame {
             idx,
-            memory: self.memory.nest_dynamic(),
+            memory,
             len: 0,
         })
     }
@@ -167,19 +244,100 @@ where
 ///
 /// [`Frame::output`]: ../traits/trait.Frame.html#method.output
 pub struct Output<'frame> {
-    pub(crate) offset: usize,
+    pub(crate) slot: *mut *mut c_void,
     _marker: PhantomData<&'frame ()>,
 }
 
 impl<'frame> Output<'frame> {
-    pub(crate) unsafe fn new(offset: usize) -> Self {
+    pub(crate) unsafe fn new(slot: *mut *mut c_void) -> Self {
         Output {
-            offset,
+            slot,
             _marker: PhantomData,
         }
     }
 }
 
+/// A `Slot` is a slot of a frame that has been reserved and can be reused. A value that is
+/// assigned to a slot is protected from garbage collection until another value is assigned to
+/// it, or until the frame the slot belongs to goes out of scope. You can create a slot by calling
+/// [`Frame::reserve_slot`].
+///
+/// The values that are returned by the methods of a slot borrow it, so the slot can't be
+/// reassigned while they're in use. This means that the result of calling a function with the
+/// current contents of a slot can't be assigned to that same slot directly; a second slot can be
+/// used to hold the result until it's assigned to the first one with [`Slot::set`]:
+///
+/// ```no_run
+/// # use jlrs::prelude::*;
+/// # fn main() {
+/// # let mut julia = unsafe { Julia::init(16).unwrap() };
+/// julia.frame(3, |global, frame| {
+///     let func = Module::base(global).function("sqrt")?;
+///     let mut state = frame.reserve_slot()?;
+///     let mut result = frame.reserve_slot()?;
+///
+///     let x = Value::new(frame, 1e100f64)?;
+///     state.set(frame, x);
+///
+///     for _ in 0..4 {
+///         let x = state.get().unwrap();
+///         let y = func.with_output(result.output()).call1(frame, x).unwrap();
+///         state.set(frame, y);
+///     }
+///
+///     state.get().unwrap().cast::<f64>()
+/// }).unwrap();
+/// # }
+/// ```
+///
+/// [`Frame::reserve_slot`]: ../traits/trait.Frame.html#method.reserve_slot
+/// [`Slot::set`]: struct.Slot.html#method.set
+pub struct Slot<'frame> {
+    slot: *mut *mut c_void,
+    _marker: PhantomData<&'frame ()>,
+}
+
+impl<'frame> Slot<'frame> {
+    pub(crate) unsafe fn new(slot: *mut *mut c_void) -> Self {
+        Slot {
+            slot,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Assign `value` to this slot, the previous contents of the slot are no longer protected by
+    /// it.
+    pub fn set<'slot, 'fr, F>(
+        &'slot mut self,
+        frame: &mut F,
+        value: Value<'_, 'static>,
+    ) -> Value<'slot, 'static>
+    where
+        F: Frame<'fr>,
+    {
+        unsafe { frame.assign_output(Output::new(self.slot), value.ptr(), Internal) }
+    }
+
+    /// Returns the value that has been assigned to this slot, or `None` if it's empty.
+    pub fn get<'slot>(&'slot self) -> Option<Value<'slot, 'static>> {
+        unsafe {
+            let value = *self.slot;
+            if value.is_null() {
+                None
+            } else {
+                Some(Value::wrap(value.cast()))
+            }
+        }
+    }
+
+    /// Returns an `Output` that assigns a value to this slot. This can be used to store the
+    /// result of a function call in the slot directly, the previous contents of the slot are no
+    /// longer protected by it.
+    pub fn output<'slot>(&'slot mut self) -> Output<'slot> {
+        unsafe { Output::new(self.slot) }
+    }
+}
+
 /// A `NullFrame` can be used if you call Rust from Julia through `ccall` and want to borrow array
 /// data but not perform any allocations. It can't be nested or be used for functions that
 /// allocate (like creating new values or calling functions). Functions that depend on allocation
@@ -212,10 +370,11 @@ impl<'frame> AsyncFrame<'frame> {
     pub(crate) unsafe fn nested_frame<'nested>(
         &'nested mut self,
     ) -> JlrsResult<DynamicFrame<'nested, Async>> {
-        let idx = self.memory.new_frame()?;
+        let mut memory = self.memory.nest_dynamic();
+        let idx = memory.new_frame()?;
         Ok(DynamicFrame {
             idx,
-            memory: self.memory.nest_dynamic(),
+            memory,
             len: 0,
         })
     }
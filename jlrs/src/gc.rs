@@ -0,0 +1,212 @@
+//! Control Julia's garbage collector.
+//!
+//! The garbage collector normally runs whenever Julia decides it's necessary. With the methods
+//! of the [`Gc`] trait, which is implemented by [`Julia`], [`CCall`], and all frames, a
+//! collection can be triggered manually, the GC can be disabled temporarily, and statistics can
+//! be read. Because the frames implement this trait, these methods can also be used inside tasks
+//! that are executed by the async runtime; [`AsyncJulia`] also has methods to trigger a
+//! collection and enable or disable the GC.
+//!
+//! [`Gc`]: trait.Gc.html
+//! [`Julia`]: ../struct.Julia.html
+//! [`CCall`]: ../struct.CCall.html
+//! [`AsyncJulia`]: ../multitask/struct.AsyncJulia.html
+
+use crate::error::{JlrsError, JlrsResult};
+#[cfg(all(feature = "async", target_os = "linux"))]
+use crate::frame::AsyncFrame;
+use crate::frame::{DynamicFrame, NullFrame, StaticFrame};
+use crate::global::Global;
+use crate::mode::Mode;
+use crate::traits::{private, Frame};
+use crate::value::module::Module;
+use crate::value::Value;
+use crate::{CCall, Julia};
+use jl_sys::{
+    jl_gc_collect, jl_gc_collection_t, jl_gc_collection_t_JL_GC_AUTO,
+    jl_gc_collection_t_JL_GC_FULL, jl_gc_collection_t_JL_GC_INCREMENTAL, jl_gc_enable,
+    jl_gc_is_enabled,
+};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// The kind of collection that is performed by [`Gc::gc_collect`].
+///
+/// [`Gc::gc_collect`]: trait.Gc.html#method.gc_collect
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GcCollection {
+    /// Let the GC decide whether a full or an incremental collection is performed.
+    Auto,
+    /// Perform a full collection.
+    Full,
+    /// Perform an incremental collection, only young objects are collected.
+    Incremental,
+}
+
+impl GcCollection {
+    pub(crate) fn as_jl_gc_collection(self) -> jl_gc_collection_t {
+        match self {
+            GcCollection::Auto => jl_gc_collection_t_JL_GC_AUTO,
+            GcCollection::Full => jl_gc_collection_t_JL_GC_FULL,
+            GcCollection::Incremental => jl_gc_collection_t_JL_GC_INCREMENTAL,
+        }
+    }
+}
+
+/// Statistics of the garbage collector, returned by [`Gc::gc_stats`]. These are read from the
+/// result of `Base.gc_num`, all values are totals since Julia has been initialized.
+///
+/// [`Gc::gc_stats`]: trait.Gc.html#method.gc_stats
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GcStats {
+    /// The number of bytes that have been allocated.
+    pub allocated_bytes: u64,
+    /// The number of bytes that have been freed.
+    pub freed_bytes: u64,
+    /// The number of times the GC has run.
+    pub collections: u64,
+    /// The number of times the GC has performed a full collection.
+    pub full_collections: u64,
+    /// The total time spent collecting garbage.
+    pub total_pause: Duration,
+}
+
+/// Disables the GC until it's dropped, after which the GC is restored to its previous state.
+/// This guard is returned by [`Gc::disable_gc`].
+///
+/// [`Gc::disable_gc`]: trait.Gc.html#method.disable_gc
+#[must_use]
+pub struct GcGuard {
+    was_enabled: bool,
+    // The state of the GC must be changed from a thread that Julia knows about.
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Drop for GcGuard {
+    fn drop(&mut self) {
+        unsafe {
+            jl_gc_enable(self.was_enabled as _);
+        }
+    }
+}
+
+/// Control the garbage collector. This trait is implemented by [`Julia`], [`CCall`], and all
+/// frames.
+///
+/// [`Julia`]: ../struct.Julia.html
+/// [`CCall`]: ../struct.CCall.html
+pub trait Gc: private::Gc {
+    /// Enable or disable the GC. Returns whether the GC was enabled before this method was
+    /// called.
+    fn enable_gc(&mut self, on: bool) -> bool {
+        unsafe { jl_gc_enable(on as _) == 1 }
+    }
+
+    /// Returns `true` if the GC is enabled.
+    fn gc_is_enabled(&self) -> bool {
+        unsafe { jl_gc_is_enabled() == 1 }
+    }
+
+    /// Disable the GC until the returned guard is dropped. This can be used to make sure the GC
+    /// doesn't run during a critical section. While the GC is disabled memory is never freed,
+    /// so keep the guard alive for as short as possible.
+    fn disable_gc(&mut self) -> GcGuard {
+        GcGuard {
+            was_enabled: self.enable_gc(false),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Force a collection of the given kind. Nothing happens if the GC is disabled.
+    fn gc_collect(&mut self, mode: GcCollection) {
+        unsafe { jl_gc_collect(mode.as_jl_gc_collection()) }
+    }
+
+    /// Returns the current statistics of the GC. A new dynamic frame is created to read them, so
+    /// this requires at least eight free slots on the GC stack. Returns an error if there isn't
+    /// enough space on the stack or the frame doesn't support nesting.
+    fn gc_stats(&mut self) -> JlrsResult<GcStats>;
+}
+
+impl private::Gc for Julia {}
+impl private::Gc for CCall {}
+impl<'frame, M: Mode> private::Gc for StaticFrame<'frame, M> {}
+impl<'frame, M: Mode> private::Gc for DynamicFrame<'frame, M> {}
+impl<'frame> private::Gc for NullFrame<'frame> {}
+#[cfg(all(feature = "async", target_os = "linux"))]
+impl<'frame> private::Gc for AsyncFrame<'frame> {}
+
+impl Gc for Julia {
+    fn gc_stats(&mut self) -> JlrsResult<GcStats> {
+        self.dynamic_frame(|_, frame| read_stats(frame))
+    }
+}
+
+impl Gc for CCall {
+    fn gc_stats(&mut self) -> JlrsResult<GcStats> {
+        self.dynamic_frame(|_, frame| read_stats(frame))
+    }
+}
+
+impl<'frame, M: Mode> Gc for StaticFrame<'frame, M> {
+    fn gc_stats(&mut self) -> JlrsResult<GcStats> {
+        self.dynamic_frame(read_stats)
+    }
+}
+
+impl<'frame, M: Mode> Gc for DynamicFrame<'frame, M> {
+    fn gc_stats(&mut self) -> JlrsResult<GcStats> {
+        self.dynamic_frame(read_stats)
+    }
+}
+
+impl<'frame> Gc for NullFrame<'frame> {
+    fn gc_stats(&mut self) -> JlrsResult<GcStats> {
+        Err(JlrsError::NullFrame)?
+    }
+}
+
+#[cfg(all(feature = "async", target_os = "linux"))]
+impl<'frame> Gc for AsyncFrame<'frame> {
+    fn gc_stats(&mut self) -> JlrsResult<GcStats> {
+        self.dynamic_frame(read_stats)
+    }
+}
+
+// The fields of `Base.GC_Num` have different integer types.
+fn as_u64(value: Value) -> JlrsResult<u64> {
+    if value.is::<u64>() {
+        value.cast::<u64>()
+    } else if value.is::<i64>() {
+        Ok(value.cast::<i64>()? as u64)
+    } else if value.is::<u32>() {
+        Ok(value.cast::<u32>()? as u64)
+    } else if value.is::<i32>() {
+        Ok(value.cast::<i32>()? as u64)
+    } else {
+        Err(JlrsError::WrongType)?
+    }
+}
+
+fn read_stats<'frame, F>(frame: &mut F) -> JlrsResult<GcStats>
+where
+    F: Frame<'frame>,
+{
+    let global = unsafe { Global::new() };
+    let base = Module::base(global);
+    let exception = |e: Value| JlrsError::Exception(e.type_name().into());
+
+    let num = base.function("gc_num")?.call0(frame)?.map_err(exception)?;
+    let allocated = base
+        .function("gc_total_bytes")?
+        .call1(frame, num)?
+        .map_err(exception)?;
+
+    Ok(GcStats {
+        allocated_bytes: as_u64(allocated)?,
+        freed_bytes: as_u64(num.get_field(frame, "freed")?)?,
+        collections: as_u64(num.get_field(frame, "pause")?)?,
+        full_collections: as_u64(num.get_field(frame, "full_sweep")?)?,
+        total_pause: Duration::from_nanos(as_u64(num.get_field(frame, "total_time")?)?),
+    })
+}
@@ -0,0 +1,90 @@
+//! Interrupt function calls that take too long.
+//!
+//! A Julia function that never returns blocks the thread it's called from forever. With
+//! [`Value::call_timeout`] and [`Value::call_interruptible`] a function is called in a new Julia
+//! task, which is interrupted by throwing an `InterruptException` when the timeout has expired
+//! or when [`Interrupt::interrupt`] is called from another thread. If the function call is
+//! interrupted, these methods return [`JlrsError::Interrupted`]. Functions can also be called
+//! this way on another thread with [`Value::call_async_interruptible`].
+//!
+//! Interruption is cooperative: the exception can only be thrown when the task yields, for
+//! example because it performs IO, sleeps or calls `yield` explicitly. A function that never
+//! yields, like `while true end`, can't be interrupted: the timeout expires, but the call only
+//! returns when the function does. Julia itself can't do better, a `SIGINT` is also only handled
+//! when the thread reaches a safepoint, which a loop like this never does, unless the exception
+//! is forcibly thrown at an arbitrary point in the code. Because that can leave the runtime in an
+//! inconsistent state jlrs doesn't use signals. Code that must be contained even if it never
+//! yields should be run in a separate process. You must include `jlrs.jl` to use this module.
+//!
+//! The Julia task that calls the function holds a reference to the flag of the [`Interrupt`], so
+//! it can still be interrupted if the future returned by
+//! [`Value::call_async_interruptible`] is dropped before the call has completed. The task keeps
+//! running in that case until the function returns or is interrupted.
+//!
+//! [`Value::call_timeout`]: ../value/struct.Value.html#method.call_timeout
+//! [`Value::call_interruptible`]: ../value/struct.Value.html#method.call_interruptible
+//! [`Value::call_async_interruptible`]: ../value/struct.Value.html#method.call_async_interruptible
+//! [`Interrupt::interrupt`]: struct.Interrupt.html#method.interrupt
+//! [`Interrupt`]: struct.Interrupt.html
+//! [`JlrsError::Interrupted`]: ../error/enum.JlrsError.html#variant.Interrupted
+
+use crate::error::{JlrsError, JlrsResult};
+use crate::value::CallResult;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A handle that can be used to interrupt a function call from another thread. Cloning an
+/// `Interrupt` returns a handle to the same flag.
+#[derive(Clone, Default, Debug)]
+pub struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+    /// Create a new handle.
+    pub fn new() -> Self {
+        Interrupt::default()
+    }
+
+    /// Interrupt the function calls that use this handle. The function is interrupted the next
+    /// time the task that calls it yields. Function calls that use this handle and start after
+    /// this method has been called are interrupted immediately until [`Interrupt::reset`] is
+    /// called.
+    ///
+    /// [`Interrupt::reset`]: struct.Interrupt.html#method.reset
+    pub fn interrupt(&self) {
+        self.0.store(true, Ordering::SeqCst)
+    }
+
+    /// Returns `true` if [`Interrupt::interrupt`] has been called.
+    ///
+    /// [`Interrupt::interrupt`]: struct.Interrupt.html#method.interrupt
+    pub fn is_interrupted(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Reset the handle so it can be reused.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::SeqCst)
+    }
+
+    // Returns a new reference to the flag, which `Jlrs.interruptiblecall` reads as a `UInt8`.
+    // It must be released with `release_flag`.
+    pub(crate) fn flag_ptr(&self) -> *mut c_void {
+        Arc::into_raw(self.0.clone()) as *mut c_void
+    }
+}
+
+// Called by `Jlrs.interruptiblecall` to release the reference to the flag it owns.
+pub(crate) unsafe extern "C" fn release_flag(flag: *const AtomicBool) {
+    drop(Arc::from_raw(flag));
+}
+
+// Converts an `InterruptException` thrown by `Jlrs.interruptiblecall` to an error.
+pub(crate) fn check_interrupted<'frame, 'data>(
+    res: CallResult<'frame, 'data>,
+) -> JlrsResult<CallResult<'frame, 'data>> {
+    match res {
+        Err(exc) if exc.type_name() == "InterruptException" => Err(JlrsError::Interrupted)?,
+        res => Ok(res),
+    }
+}
@@ -18,6 +18,8 @@
 //!  - Support for mapping Julia structs to Rust structs, which can be generated with `JlrsReflect.jl`.
 //!  - Structs that can be mapped to Rust include those with type parameters and bits unions.
 //!  - Use these features when calling Rust from Julia through `ccall`.
+//!  - Redirect the output Julia writes to `stdout` and `stderr` to Rust.
+//!  - Forward messages logged by Julia to the `log` crate by enabling the `log` feature.
 //!  - Offload long-running functions to another thread and `.await` the result with the (experimental) async runtime.
 //!
 //!
@@ -68,7 +70,8 @@
 //! [`Julia::init`]. Note that this method can only be called once, if you drop [`Julia`] you won't
 //! be able to create a new one and have to restart the entire program. If you want to use a
 //! custom system image, you must call [`Julia::init_with_image`] instead of [`Julia::init`].
-//! If you're calling Rust from Julia everything has already been initialized, you can use `CCall`
+//! Options that must be set before Julia is initialized, like the number of threads, can be set
+//! with [`JuliaBuilder`]. If you're calling Rust from Julia everything has already been initialized, you can use `CCall`
 //! instead.
 //!
 //! ## Calling Julia from Rust
@@ -85,8 +88,8 @@
 //!
 //! Local data must be handled properly: Julia is a programming language with a garbage collector
 //! that is unaware of any references to data outside of Julia. In order to make it aware of this
-//! usage a stack must be maintained. You choose this stack's size when calling [`Julia::init`].
-//! The elements of this stack are called stack frames; they contain a pointer to the previous
+//! usage a stack must be maintained. You choose this stack's initial size when calling
+//! [`Julia::init`], it grows when it runs out of space. The elements of this stack are called stack frames; they contain a pointer to the previous
 //! frame, the number of protected values, and that number of pointers to values. The two frame
 //! types offered by jlrs take care of all the technical details, a [`DynamicFrame`] will grow
 //! to the required size while a [`StaticFrame`] has a definite number of slots. These frames can
@@ -328,6 +331,7 @@
 //! [`CCall`]: struct.CCall.html
 //! [`Julia::init`]: struct.Julia.html#method.init
 //! [`Julia::init_with_image`]: struct.Julia.html#method.init_with_image
+//! [`JuliaBuilder`]: struct.JuliaBuilder.html
 //! [`Julia::include`]: struct.Julia.html#method.include
 //! [`Julia::frame`]: struct.Julia.html#method.frame
 //! [`Julia::dynamic_frame`]: struct.Julia.html#method.dynamic_frame
@@ -359,32 +363,50 @@
 
 pub mod error;
 pub mod frame;
+pub mod gc;
 pub mod global;
+pub mod interrupt;
 #[doc(hidden)]
 pub mod jl_sys_export;
 #[cfg(all(feature = "async", target_os = "linux"))]
 pub mod julia_future;
+#[cfg(feature = "log")]
+pub mod logging;
 pub mod mode;
 #[cfg(all(feature = "async", target_os = "linux"))]
 pub mod multitask;
+pub mod persistent;
 pub mod prelude;
+pub mod redirect;
 mod stack;
 pub mod traits;
 #[doc(hidden)]
 pub mod util;
 pub mod value;
+pub mod version;
 
 use error::{JlrsError, JlrsResult};
 use frame::{DynamicFrame, NullFrame, StaticFrame};
 use global::Global;
-use jl_sys::{jl_atexit_hook, jl_init, jl_init_with_image__threading, jl_is_initialized};
+use jl_sys::{
+    jl_atexit_hook, jl_init, jl_init_with_image__threading, jl_is_initialized, jl_options,
+    jl_parse_opts, JL_OPTIONS_CHECK_BOUNDS_OFF, JL_OPTIONS_CHECK_BOUNDS_ON,
+    JL_OPTIONS_STARTUPFILE_OFF, JL_OPTIONS_STARTUPFILE_ON,
+};
 use mode::Sync;
+use redirect::{SharedBuffer, Sink, Sinks, Stream};
 use stack::{Dynamic, RawStack, StackView, Static};
+use std::env;
+use std::ffi::CString;
+use std::io::Write;
 use std::io::{Error as IOError, ErrorKind};
-use std::path::Path;
+use std::os::raw::c_int;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use traits::Frame;
 use value::module::Module;
-use value::Value;
+use value::{CallResult, Value};
+use version::JuliaVersion;
 
 pub(crate) static INIT: AtomicBool = AtomicBool::new(false);
 
@@ -397,6 +419,7 @@ pub(crate) static INIT: AtomicBool = AtomicBool::new(false);
 /// [`Julia::init_with_image`]: struct.Julia.html#method.init_with_image
 pub struct Julia {
     stack: RawStack,
+    sinks: Sinks,
 }
 
 impl Julia {
@@ -404,19 +427,27 @@ impl Julia {
     /// will return an error. If this struct is dropped, you will need to restart your program to
     /// be able to call Julia code again.
     ///
-    /// You have to choose a stack size when calling this function. This will be the total number
-    /// of slots that will be available for the GC stack. One of these slots will always be in
-    /// use. Each frame needs two slots of overhead, plus one for every value created with that
+    /// You have to choose a stack size when calling this function. This will be the initial
+    /// number of slots that will be available for the GC stack. One of these slots will always be
+    /// in use. Each frame needs two slots of overhead, plus one for every value created with that
     /// frame. A [`StaticFrame`] preallocates its slots, while a [`DynamicFrame`] grows to the
     /// required size. If calling a method requires one or more slots, this amount is explicitly
-    /// documented.
+    /// documented. The stack grows when it runs out of slots, its total size can be limited with
+    /// [`Julia::set_max_stack_size`].
+    ///
+    /// Returns an error if the version of Julia that is used isn't compatible with jlrs, see the
+    /// [`version`] module for more information.
     ///
     /// This function is unsafe because this crate provides you with a way to execute arbitrary
     /// Julia code which can't be checked for correctness.
     ///
     /// [`StaticFrame`]: frame/struct.StaticFrame.html
     /// [`DynamicFrame`]: frame/struct.DynamicFrame.html
+    /// [`version`]: version/index.html
+    /// [`Julia::set_max_stack_size`]: struct.Julia.html#method.set_max_stack_size
     pub unsafe fn init(stack_size: usize) -> JlrsResult<Self> {
+        version::check_version()?;
+
         if jl_is_initialized() != 0 || INIT.swap(true, Ordering::SeqCst) {
             return Err(JlrsError::AlreadyInitialized.into());
         }
@@ -425,6 +456,7 @@ impl Julia {
 
         Ok(Julia {
             stack: RawStack::new(stack_size),
+            sinks: Sinks::default(),
         })
     }
 
@@ -438,8 +470,8 @@ impl Julia {
     /// directory that contains a compatible Julia binary (eg `${JULIA_DIR}/bin`), the second must
     /// be either an absolute or a relative path to a system image.
     ///
-    /// This function will return an error if either of the two paths does not exist or if Julia
-    /// has already been initialized.
+    /// This function will return an error if either of the two paths does not exist, if Julia
+    /// has already been initialized, or if the version of Julia isn't compatible with jlrs.
     ///
     /// [`Julia::init`]: struct.Julia.html#init
     /// [`PackageCompiler`]: https://julialang.github.io/PackageCompiler.jl/dev/
@@ -448,6 +480,8 @@ impl Julia {
         julia_bindir: P,
         image_path: P,
     ) -> JlrsResult<Self> {
+        version::check_version()?;
+
         if INIT.swap(true, Ordering::SeqCst) {
             Err(JlrsError::AlreadyInitialized)?;
         }
@@ -472,19 +506,38 @@ impl Julia {
 
         Ok(Julia {
             stack: RawStack::new(stack_size),
+            sinks: Sinks::default(),
         })
     }
 
-    /// Change the stack size to `stack_size`.
+    /// Change the stack size to `stack_size`, the maximum size is left unchanged.
     pub fn set_stack_size(&mut self, stack_size: usize) {
-        unsafe { self.stack = RawStack::new(stack_size) }
+        unsafe { self.stack = RawStack::with_max_size(stack_size, self.stack.max_size()) }
     }
 
-    /// Returns the current stack size.
+    /// Returns the current stack size. The stack grows when it runs out of slots, so this can be
+    /// larger than the size it was created with.
     pub fn stack_size(&self) -> usize {
         self.stack.size()
     }
 
+    /// Limit the total size of the stack to `max_size` slots, or remove the limit if it's
+    /// `None`. By default the size is unlimited. If the limit has been reached creating a new
+    /// frame or growing a dynamic frame returns an error.
+    pub fn set_max_stack_size(&mut self, max_size: Option<usize>) {
+        self.stack.set_max_size(max_size)
+    }
+
+    /// Returns the maximum size of the stack, or `None` if its size is unlimited.
+    pub fn max_stack_size(&self) -> Option<usize> {
+        self.stack.max_size()
+    }
+
+    /// Returns the version of Julia that is used.
+    pub fn version(&self) -> JuliaVersion {
+        JuliaVersion::runtime()
+    }
+
     /// Calls `include` in the `Main` module in Julia, which executes the file's contents in that
     /// module. This has the same effect as calling `include` in the Julia REPL.
     ///
@@ -518,6 +571,317 @@ impl Julia {
         Err(JlrsError::IncludeNotFound(path.as_ref().to_string_lossy().into()).into())
     }
 
+    /// Parse and evaluate `code` in the module returned by `module`. If the code contains
+    /// multiple expressions, all of them are evaluated. The result of the last expression, or the
+    /// exception if one is thrown, is passed to `func` together with the dynamic frame it's
+    /// rooted in. Returns the result of `func`, or an error if `module` returns one or the frame
+    /// can't be created.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jlrs::prelude::*;
+    /// # fn main() {
+    /// # let mut julia = unsafe { Julia::init(16).unwrap() };
+    /// let two = julia
+    ///     .eval_string(
+    ///         |global| Ok(Module::main(global)),
+    ///         "add_one(x) = x + 1; add_one(1)",
+    ///         |_global, _frame, result| result.unwrap().cast::<i64>(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(two, 2);
+    /// # }
+    /// ```
+    pub fn eval_string<'base, 'julia: 'base, T, M, C, F>(
+        &'julia mut self,
+        module: M,
+        code: C,
+        func: F,
+    ) -> JlrsResult<T>
+    where
+        M: FnOnce(Global<'base>) -> JlrsResult<Module<'base>>,
+        C: AsRef<str>,
+        F: FnOnce(
+            Global<'base>,
+            &mut DynamicFrame<'base, Sync>,
+            CallResult<'base, 'static>,
+        ) -> JlrsResult<T>,
+    {
+        self.include_string(module, code, "string", func)
+    }
+
+    /// Parse and evaluate `code` in the module returned by `module`, `filename` is used as the
+    /// name of the file in backtraces and error messages. This has the same effect as calling
+    /// `include` in that module with a file that contains `code`. The result of the last
+    /// expression, or the exception if one is thrown, is passed to `func` together with the
+    /// dynamic frame it's rooted in. Exceptions are wrapped in a `LoadError`, the original
+    /// exception is its `error` field. Returns the result of `func`, or an error if `module`
+    /// returns one or the frame can't be created.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jlrs::prelude::*;
+    /// # fn main() {
+    /// # let mut julia = unsafe { Julia::init(16).unwrap() };
+    /// let exception = julia
+    ///     .include_string(
+    ///         |global| Module::main(global).submodule("Base"),
+    ///         "throw(ArgumentError(\"oops\"))",
+    ///         "oops.jl",
+    ///         |_global, frame, result| {
+    ///             let error = result.unwrap_err().get_field(frame, "error")?;
+    ///             Ok(error.type_name().to_string())
+    ///         },
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(exception, "ArgumentError");
+    /// # }
+    /// ```
+    pub fn include_string<'base, 'julia: 'base, T, M, C, N, F>(
+        &'julia mut self,
+        module: M,
+        code: C,
+        filename: N,
+        func: F,
+    ) -> JlrsResult<T>
+    where
+        M: FnOnce(Global<'base>) -> JlrsResult<Module<'base>>,
+        C: AsRef<str>,
+        N: AsRef<str>,
+        F: FnOnce(
+            Global<'base>,
+            &mut DynamicFrame<'base, Sync>,
+            CallResult<'base, 'static>,
+        ) -> JlrsResult<T>,
+    {
+        self.dynamic_frame(|global, frame| {
+            let module = module(global)?;
+            let result = frame.include_string(module, code, filename)?;
+            func(global, frame, result)
+        })
+    }
+
+    /// Load the package or module named `name` and make it and its exported names available in
+    /// `Main`, this has the same effect as evaluating `using Foo` in the Julia REPL. Submodules
+    /// can be loaded by separating their names with dots, eg `"Foo.Bar"`. Packages are looked up
+    /// in the active project, see [`Julia::activate_project`]. After it has been loaded, the
+    /// package can be accessed with [`Module::require`].
+    ///
+    /// Returns an error if `name` is not a valid module name or the package can't be loaded.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jlrs::prelude::*;
+    /// # fn main() {
+    /// # let mut julia = unsafe { Julia::init(16).unwrap() };
+    /// julia.using("LinearAlgebra").unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Julia::activate_project`]: struct.Julia.html#method.activate_project
+    /// [`Module::require`]: value/module/struct.Module.html#method.require
+    pub fn using<N: AsRef<str>>(&mut self, name: N) -> JlrsResult<()> {
+        let name = name.as_ref();
+        let valid = name.split('.').all(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(c) if c.is_alphabetic() || c == '_' => {
+                    chars.all(|c| c.is_alphanumeric() || c == '_' || c == '!')
+                }
+                _ => false,
+            }
+        });
+
+        if !valid {
+            Err(JlrsError::LoadError(
+                name.into(),
+                "invalid module name".into(),
+            ))?;
+        }
+
+        self.frame(3, |global, frame| {
+            let code = format!("using {}", name);
+            match frame.eval_string(Module::main(global), code)? {
+                Ok(_) => Ok(()),
+                Err(e) => Err(JlrsError::LoadError(name.into(), e.type_name().into()).into()),
+            }
+        })
+    }
+
+    /// Activate the project at `path`, packages loaded with [`Julia::using`] and
+    /// [`Module::require`] are looked up in its environment. This has the same effect as setting
+    /// `Base.ACTIVE_PROJECT`, or starting Julia with the `--project` option. It can be used to
+    /// load packages from a local environment without using the package manager.
+    ///
+    /// Returns an error if `path` doesn't exist.
+    ///
+    /// [`Julia::using`]: struct.Julia.html#method.using
+    /// [`Module::require`]: value/module/struct.Module.html#method.require
+    pub fn activate_project<P: AsRef<Path>>(&mut self, path: P) -> JlrsResult<()> {
+        if !path.as_ref().exists() {
+            let io_err = IOError::new(ErrorKind::NotFound, path.as_ref().to_string_lossy());
+            return Err(JlrsError::other(io_err))?;
+        }
+
+        self.frame(2, |global, frame| {
+            let path = Value::new(frame, path.as_ref().to_string_lossy())?;
+            let active_project = Module::base(global).global("ACTIVE_PROJECT")?;
+            Module::base(global)
+                .function("setindex!")?
+                .call2(frame, active_project, path)?
+                .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+            Ok(())
+        })
+    }
+
+    /// Redirect Julia's `stdout` to `sink`. The redirection stays active until
+    /// [`Julia::restore_stdout`] is called, redirections can be nested. See the [`redirect`]
+    /// module for more information. You must include `jlrs.jl` to use this method.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jlrs::prelude::*;
+    /// # fn main() {
+    /// # let mut julia = unsafe { Julia::init(16).unwrap() };
+    /// julia.redirect_stdout(std::io::sink()).unwrap();
+    /// julia
+    ///     .eval_string(
+    ///         |global| Ok(Module::main(global)),
+    ///         "println(\"This is discarded\")",
+    ///         |_global, _frame, result| Ok(result.is_ok()),
+    ///     )
+    ///     .unwrap();
+    /// julia.restore_stdout().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Julia::restore_stdout`]: struct.Julia.html#method.restore_stdout
+    /// [`redirect`]: redirect/index.html
+    pub fn redirect_stdout<W: Write + Send + 'static>(&mut self, sink: W) -> JlrsResult<()> {
+        self.redirect(Stream::Stdout, Box::new(sink))
+    }
+
+    /// Redirect Julia's `stderr` to `sink`. The redirection stays active until
+    /// [`Julia::restore_stderr`] is called, redirections can be nested. See the [`redirect`]
+    /// module for more information. You must include `jlrs.jl` to use this method.
+    ///
+    /// [`Julia::restore_stderr`]: struct.Julia.html#method.restore_stderr
+    /// [`redirect`]: redirect/index.html
+    pub fn redirect_stderr<W: Write + Send + 'static>(&mut self, sink: W) -> JlrsResult<()> {
+        self.redirect(Stream::Stderr, Box::new(sink))
+    }
+
+    /// Undo the most recent redirection of `stdout`. All output that has been written before
+    /// this method is called is written to the sink, which is flushed and dropped. Does nothing
+    /// if `stdout` isn't redirected.
+    pub fn restore_stdout(&mut self) -> JlrsResult<()> {
+        self.restore(Stream::Stdout)
+    }
+
+    /// Undo the most recent redirection of `stderr`. All output that has been written before
+    /// this method is called is written to the sink, which is flushed and dropped. Does nothing
+    /// if `stderr` isn't redirected.
+    pub fn restore_stderr(&mut self) -> JlrsResult<()> {
+        self.restore(Stream::Stderr)
+    }
+
+    /// Call `func` while Julia's `stdout` and `stderr` are redirected, and return its result
+    /// together with everything that has been written to these streams in the meantime. You
+    /// must include `jlrs.jl` to use this method.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jlrs::prelude::*;
+    /// # fn main() {
+    /// # let mut julia = unsafe { Julia::init(16).unwrap() };
+    /// let (_, output) = julia
+    ///     .capture_output(|julia| {
+    ///         julia.eval_string(
+    ///             |global| Ok(Module::main(global)),
+    ///             "println(\"Hello!\")",
+    ///             |_global, _frame, result| Ok(result.is_ok()),
+    ///         )
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(output, "Hello!\n");
+    /// # }
+    /// ```
+    pub fn capture_output<T, F>(&mut self, func: F) -> JlrsResult<(T, String)>
+    where
+        F: FnOnce(&mut Julia) -> JlrsResult<T>,
+    {
+        let buffer = SharedBuffer::default();
+        self.redirect_stdout(buffer.clone())?;
+        if let Err(e) = self.redirect_stderr(buffer.clone()) {
+            self.restore_stdout()?;
+            return Err(e);
+        }
+
+        let res = func(self);
+        let restored_stderr = self.restore_stderr();
+        let restored_stdout = self.restore_stdout();
+        let res = res?;
+        restored_stderr?;
+        restored_stdout?;
+
+        Ok((res, buffer.to_string_lossy()))
+    }
+
+    /// Install a logger in Julia that forwards messages logged with macros like `@info` to the
+    /// logger used by the `log` crate. Messages below `filter` are discarded by Julia, calling
+    /// this method again replaces the filter. See the [`logging`] module for more information.
+    /// You must include `jlrs.jl` to use this method. This method is only available if the `log`
+    /// feature is enabled.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jlrs::prelude::*;
+    /// # fn main() {
+    /// # let mut julia = unsafe { Julia::init(16).unwrap() };
+    /// julia.install_logger(log::LevelFilter::Info).unwrap();
+    /// julia
+    ///     .eval_string(
+    ///         |global| Ok(Module::main(global)),
+    ///         "@info \"Forwarded to Rust\" x = 1",
+    ///         |_global, _frame, result| Ok(result.is_ok()),
+    ///     )
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`logging`]: logging/index.html
+    #[cfg(feature = "log")]
+    pub fn install_logger(&mut self, filter: log::LevelFilter) -> JlrsResult<()> {
+        self.frame(3, |_, frame| logging::install_logger(frame, filter))
+    }
+
+    fn redirect(&mut self, stream: Stream, sink: Sink) -> JlrsResult<()> {
+        let mut sink = Box::new(sink);
+        self.frame(3, |_, frame| redirect::redirect(frame, stream, &mut sink))?;
+        self.sinks.push(stream, sink);
+        Ok(())
+    }
+
+    fn restore(&mut self, stream: Stream) -> JlrsResult<()> {
+        if self.frame(1, |_, frame| redirect::restore(frame, stream))? {
+            if let Some(mut sink) = self.sinks.pop(stream) {
+                sink.flush().map_err(JlrsError::other)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Create a [`StaticFrame`] that can hold `capacity` values, and call the given closure.
     /// Returns the result of this closure, or an error if the new frame can't be created because
     /// there's not enough space on the GC stack. The number of required slots on the stack is
@@ -554,9 +918,8 @@ impl Julia {
         F: FnOnce(Global<'base>, &mut StaticFrame<'base, Sync>) -> JlrsResult<T>,
     {
         unsafe {
-            let d = self.stack.as_mut();
             let global = Global::new();
-            let mut view = StackView::<Sync, Static>::new(d);
+            let mut view = StackView::<Sync, Static>::new(&mut self.stack);
             let frame_idx = view.new_frame(capacity)?;
             let mut frame = StaticFrame::with_capacity(frame_idx, capacity, view);
             func(global, &mut frame)
@@ -593,9 +956,8 @@ impl Julia {
         F: FnOnce(Global<'base>, &mut DynamicFrame<'base, Sync>) -> JlrsResult<T>,
     {
         unsafe {
-            let d = self.stack.as_mut();
             let global = Global::new();
-            let mut view = StackView::<Sync, Dynamic>::new(d);
+            let mut view = StackView::<Sync, Dynamic>::new(&mut self.stack);
             let frame_idx = view.new_frame()?;
             let mut frame = DynamicFrame::new(frame_idx, view);
             func(global, &mut frame)
@@ -611,6 +973,275 @@ impl Drop for Julia {
     }
 }
 
+/// A builder that lets you configure Julia before it's initialized. [`Julia::init`] and
+/// [`Julia::init_with_image`] only let you choose the stack size and a custom system image,
+/// while many other options that are normally set with command line arguments, like the number
+/// of threads and the optimization level, can only be set before Julia is initialized. These
+/// options can be set with this builder, [`JuliaBuilder::start`] initializes Julia and returns
+/// [`Julia`].
+///
+/// Example:
+///
+/// ```no_run
+/// # use jlrs::prelude::*;
+/// # fn main() {
+/// let julia = unsafe {
+///     JuliaBuilder::new(16)
+///         .n_threads(4)
+///         .optimization_level(3)
+///         .arg("--compile=min")
+///         .start()
+///         .unwrap()
+/// };
+/// # }
+/// ```
+///
+/// [`Julia`]: struct.Julia.html
+/// [`Julia::init`]: struct.Julia.html#method.init
+/// [`Julia::init_with_image`]: struct.Julia.html#method.init_with_image
+/// [`JuliaBuilder::start`]: struct.JuliaBuilder.html#method.start
+pub struct JuliaBuilder {
+    stack_size: usize,
+    max_stack_size: Option<usize>,
+    image: Option<(PathBuf, PathBuf)>,
+    n_threads: Option<usize>,
+    opt_level: Option<u8>,
+    startup_file: Option<bool>,
+    check_bounds: Option<bool>,
+    depot_path: Option<PathBuf>,
+    project: Option<PathBuf>,
+    args: Vec<String>,
+}
+
+impl JuliaBuilder {
+    /// Create a new builder. The stack size has the same meaning as it does for
+    /// [`Julia::init`]. All other options are left unset, in which case Julia uses its default
+    /// value (or the value of the corresponding environment variable).
+    ///
+    /// [`Julia::init`]: struct.Julia.html#method.init
+    pub fn new(stack_size: usize) -> Self {
+        JuliaBuilder {
+            stack_size,
+            max_stack_size: None,
+            image: None,
+            n_threads: None,
+            opt_level: None,
+            startup_file: None,
+            check_bounds: None,
+            depot_path: None,
+            project: None,
+            args: Vec::new(),
+        }
+    }
+
+    /// Limit the total size of the GC stack to `max_size` slots, see
+    /// [`Julia::set_max_stack_size`].
+    ///
+    /// [`Julia::set_max_stack_size`]: struct.Julia.html#method.set_max_stack_size
+    pub fn max_stack_size(mut self, max_size: usize) -> Self {
+        self.max_stack_size = Some(max_size);
+        self
+    }
+
+    /// Load a custom system image rather than the default one. The arguments have the same
+    /// meaning as they do for [`Julia::init_with_image`].
+    ///
+    /// [`Julia::init_with_image`]: struct.Julia.html#method.init_with_image
+    pub fn image<P: AsRef<Path>, Q: AsRef<Path>>(mut self, julia_bindir: P, image_path: Q) -> Self {
+        self.image = Some((
+            julia_bindir.as_ref().to_path_buf(),
+            image_path.as_ref().to_path_buf(),
+        ));
+        self
+    }
+
+    /// Set the number of threads Julia can use, this is equivalent to setting the
+    /// `JULIA_NUM_THREADS` environment variable or the `--threads` command line option.
+    pub fn n_threads(mut self, n_threads: usize) -> Self {
+        self.n_threads = Some(n_threads);
+        self
+    }
+
+    /// Set the optimization level, which must be between 0 and 3. This is equivalent to the
+    /// `--optimize` command line option. [`JuliaBuilder::start`] returns an error if the level
+    /// is larger than 3.
+    ///
+    /// [`JuliaBuilder::start`]: struct.JuliaBuilder.html#method.start
+    pub fn optimization_level(mut self, opt_level: u8) -> Self {
+        self.opt_level = Some(opt_level);
+        self
+    }
+
+    /// If `true`, `~/.julia/config/startup.jl` is loaded after Julia has been initialized. It's
+    /// not loaded by default. This is equivalent to the `--startup-file` command line option.
+    pub fn startup_file(mut self, load: bool) -> Self {
+        self.startup_file = Some(load);
+        self
+    }
+
+    /// Enable or disable bounds checking everywhere. If this option is not set, `@inbounds`
+    /// annotations are respected. This is equivalent to the `--check-bounds` command line option.
+    pub fn check_bounds(mut self, check: bool) -> Self {
+        self.check_bounds = Some(check);
+        self
+    }
+
+    /// Set the depot path, this is equivalent to setting the `JULIA_DEPOT_PATH` environment
+    /// variable.
+    pub fn depot_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.depot_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the project that is activated when Julia starts, this is equivalent to setting the
+    /// `JULIA_PROJECT` environment variable. See [`Julia::activate_project`] for more
+    /// information.
+    ///
+    /// [`Julia::activate_project`]: struct.Julia.html#method.activate_project
+    pub fn project<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.project = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add a command line option, eg `"--compile=min"`. Options are parsed in the order they're
+    /// added, before the other options of this builder are applied. Julia will abort if an option
+    /// is invalid.
+    pub fn arg<S: Into<String>>(mut self, arg: S) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add several command line options, see [`JuliaBuilder::arg`].
+    ///
+    /// [`JuliaBuilder::arg`]: struct.JuliaBuilder.html#method.arg
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Initialize Julia with the options that have been set, this function can only be called
+    /// once. Returns an error if Julia has already been initialized, if the version of Julia isn't
+    /// compatible with jlrs, if the optimization level is larger than 3, if the paths of a custom
+    /// image don't exist, or if an argument contains a null byte.
+    ///
+    /// This function is unsafe because this crate provides you with a way to execute arbitrary
+    /// Julia code which can't be checked for correctness.
+    pub unsafe fn start(self) -> JlrsResult<Julia> {
+        match self.opt_level {
+            Some(level) if level > 3 => Err(JlrsError::InvalidOptimizationLevel(level))?,
+            _ => (),
+        }
+
+        version::check_version()?;
+
+        let image = match self.image {
+            Some((ref julia_bindir, ref image_path)) => {
+                let julia_bindir_str = julia_bindir.to_string_lossy().to_string();
+                let image_path_str = image_path.to_string_lossy().to_string();
+
+                if !julia_bindir.exists() {
+                    let io_err = IOError::new(ErrorKind::NotFound, julia_bindir_str);
+                    return Err(JlrsError::other(io_err))?;
+                }
+
+                if !image_path.exists() {
+                    let io_err = IOError::new(ErrorKind::NotFound, image_path_str);
+                    return Err(JlrsError::other(io_err))?;
+                }
+
+                let bindir = CString::new(julia_bindir_str).map_err(JlrsError::other)?;
+                let im_rel_path = CString::new(image_path_str).map_err(JlrsError::other)?;
+                Some((bindir, im_rel_path))
+            }
+            None => None,
+        };
+
+        let args = self
+            .args
+            .iter()
+            .map(|arg| CString::new(arg.as_str()).map_err(JlrsError::other))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Everything that can fail has been checked, nothing may change the global state of Julia
+        // before INIT has been set.
+        if jl_is_initialized() != 0 || INIT.swap(true, Ordering::SeqCst) {
+            return Err(JlrsError::AlreadyInitialized.into());
+        }
+
+        if !args.is_empty() {
+            // jl_options keeps pointers to the parsed arguments, so they must live as long as the
+            // process does. The first argument is skipped by the parser.
+            let mut argv = Vec::with_capacity(args.len() + 1);
+            argv.push(CString::new("julia").unwrap().into_raw());
+            argv.extend(args.into_iter().map(CString::into_raw));
+
+            let mut argc = argv.len() as c_int;
+            let mut argv_ptr = Box::leak(argv.into_boxed_slice()).as_mut_ptr();
+            jl_parse_opts(&mut argc, &mut argv_ptr);
+        }
+
+        if let Some(n_threads) = self.n_threads {
+            jl_options.nthreads = n_threads as _;
+        }
+
+        if let Some(opt_level) = self.opt_level {
+            jl_options.opt_level = opt_level as _;
+        }
+
+        if let Some(load) = self.startup_file {
+            jl_options.startupfile = if load {
+                JL_OPTIONS_STARTUPFILE_ON as _
+            } else {
+                JL_OPTIONS_STARTUPFILE_OFF as _
+            };
+        }
+
+        if let Some(check) = self.check_bounds {
+            jl_options.check_bounds = if check {
+                JL_OPTIONS_CHECK_BOUNDS_ON as _
+            } else {
+                JL_OPTIONS_CHECK_BOUNDS_OFF as _
+            };
+        }
+
+        if let Some(ref depot_path) = self.depot_path {
+            env::set_var("JULIA_DEPOT_PATH", depot_path);
+        }
+
+        if let Some(ref project) = self.project {
+            env::set_var("JULIA_PROJECT", project);
+        }
+
+        match image {
+            Some((bindir, im_rel_path)) => {
+                jl_init_with_image__threading(bindir.as_ptr(), im_rel_path.as_ptr())
+            }
+            None => jl_init(),
+        }
+
+        let mut julia = Julia {
+            stack: RawStack::with_max_size(self.stack_size, self.max_stack_size),
+            sinks: Sinks::default(),
+        };
+
+        if self.startup_file == Some(true) {
+            julia.frame(1, |global, frame| {
+                Module::base(global)
+                    .function("load_julia_startup")?
+                    .call0(frame)?
+                    .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+                Ok(())
+            })?;
+        }
+
+        Ok(julia)
+    }
+}
+
 /// When you call Rust from Julia through `ccall`, Julia has already been initialized and trying to
 /// initialize it again would cause a crash. In order to still be able to call Julia from Rust
 /// and to borrow arrays (if you pass them as `Array` rather than `Ptr{Array}`), you'll need to
@@ -629,6 +1260,7 @@ impl Drop for Julia {
 pub struct CCall {
     stack: Option<RawStack>,
     stack_size: usize,
+    max_stack_size: Option<usize>,
 }
 
 impl CCall {
@@ -642,6 +1274,7 @@ impl CCall {
         CCall {
             stack: None,
             stack_size,
+            max_stack_size: None,
         }
     }
 
@@ -657,7 +1290,9 @@ impl CCall {
     pub fn set_stack_size(&mut self, stack_size: usize) {
         self.stack_size = stack_size;
         if self.stack.is_some() {
-            unsafe { self.stack = Some(RawStack::new(stack_size)) }
+            unsafe {
+                self.stack = Some(RawStack::with_max_size(stack_size, self.max_stack_size));
+            }
         }
     }
 
@@ -666,6 +1301,25 @@ impl CCall {
         self.stack_size
     }
 
+    /// Limit the total size of the stack to `max_size` slots, or remove the limit if it's
+    /// `None`. By default the size is unlimited.
+    pub fn set_max_stack_size(&mut self, max_size: Option<usize>) {
+        self.max_stack_size = max_size;
+        if let Some(ref mut stack) = self.stack {
+            stack.set_max_size(max_size);
+        }
+    }
+
+    /// Returns the maximum size of the stack, or `None` if its size is unlimited.
+    pub fn max_stack_size(&self) -> Option<usize> {
+        self.max_stack_size
+    }
+
+    /// Returns the version of Julia that is used.
+    pub fn version(&self) -> JuliaVersion {
+        JuliaVersion::runtime()
+    }
+
     /// Create a [`StaticFrame`] that can hold `capacity` values, and call the given closure.
     /// Returns the result of this closure, or an error if the new frame can't be created because
     /// there's not enough space on the GC stack. The number of required slots on the stack is
@@ -688,9 +1342,8 @@ impl CCall {
         unsafe {
             self.ensure_init_stack()
                 .map(|s| {
-                    let d = s.as_mut();
                     let global = Global::new();
-                    let mut view = StackView::<Sync, Static>::new(d);
+                    let mut view = StackView::<Sync, Static>::new(s);
                     let frame_idx = view.new_frame(capacity)?;
                     let mut frame = StaticFrame::with_capacity(frame_idx, capacity, view);
                     func(global, &mut frame)
@@ -715,9 +1368,8 @@ impl CCall {
         unsafe {
             self.ensure_init_stack()
                 .map(|s| {
-                    let d = s.as_mut();
                     let global = Global::new();
-                    let mut view = StackView::<Sync, Dynamic>::new(d);
+                    let mut view = StackView::<Sync, Dynamic>::new(s);
                     let frame_idx = view.new_frame()?;
                     let mut frame = DynamicFrame::new(frame_idx, view);
                     func(global, &mut frame)
@@ -746,7 +1398,10 @@ impl CCall {
     fn ensure_init_stack(&mut self) -> Option<&mut RawStack> {
         if self.stack.is_none() {
             unsafe {
-                self.stack = Some(RawStack::new(self.stack_size));
+                self.stack = Some(RawStack::with_max_size(
+                    self.stack_size,
+                    self.max_stack_size,
+                ));
             }
         }
 
@@ -0,0 +1,142 @@
+//! Forward messages logged by Julia to the `log` crate.
+//!
+//! Julia's logging macros like `@info` and `@warn` write their messages to the global logger of
+//! Julia. With [`Julia::install_logger`], or its counterpart on [`AsyncJulia`], a logger is
+//! installed that forwards these messages to the logger that is used by the [`log`] crate.
+//!
+//! Every message is converted to a `log::Record`. The level of the message is mapped to the
+//! closest level in Rust, `Debug` is mapped to `Debug` and levels below `Debug` are mapped to
+//! `Trace`. The target of the record is the name of the module that logged the message, and the
+//! file and line are set to the location of the macro. Key-value pairs are converted to strings
+//! with `string` and added to the record. Messages below the level that is used when the logger
+//! is installed are filtered out by Julia, messages that are disabled in Rust are discarded
+//! before a record is created. If you use `tracing`, these records can be converted to events
+//! with the `tracing-log` crate.
+//!
+//! This module is only available if the `log` feature is enabled. You must include `jlrs.jl` to
+//! install the logger.
+//!
+//! [`Julia::install_logger`]: ../struct.Julia.html#method.install_logger
+//! [`AsyncJulia`]: ../multitask/struct.AsyncJulia.html
+//! [`log`]: https://docs.rs/log
+
+use crate::error::{JlrsError, JlrsResult};
+use crate::global::Global;
+use crate::traits::Frame;
+use crate::value::module::Module;
+use crate::value::Value;
+use log::{Level, LevelFilter, Record};
+use std::borrow::Cow;
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::slice;
+
+// The log levels used by Julia, with the exception of `Debug`, `Info`, `Warn` and `Error` these
+// levels can't be used with the logging macros.
+const BELOW_MIN_LEVEL: i32 = -1_000_001;
+const DEBUG: i32 = -1000;
+const INFO: i32 = 0;
+const WARN: i32 = 1000;
+const ERROR: i32 = 2000;
+const ABOVE_MAX_LEVEL: i32 = 1_000_001;
+
+fn min_level(filter: LevelFilter) -> i32 {
+    match filter {
+        LevelFilter::Off => ABOVE_MAX_LEVEL,
+        LevelFilter::Error => ERROR,
+        LevelFilter::Warn => WARN,
+        LevelFilter::Info => INFO,
+        LevelFilter::Debug => DEBUG,
+        LevelFilter::Trace => BELOW_MIN_LEVEL,
+    }
+}
+
+fn level(level: i32) -> Level {
+    if level < DEBUG {
+        Level::Trace
+    } else if level < INFO {
+        Level::Debug
+    } else if level < WARN {
+        Level::Info
+    } else if level < ERROR {
+        Level::Warn
+    } else {
+        Level::Error
+    }
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Cow<'a, str> {
+    CStr::from_ptr(s).to_string_lossy()
+}
+
+unsafe extern "C" fn log_record(
+    level_jl: i32,
+    message: *const c_char,
+    module: *const c_char,
+    file: *const c_char,
+    line: i64,
+    keys: *const *const c_char,
+    values: *const *const c_char,
+    n_pairs: usize,
+) {
+    let level = level(level_jl);
+    let module = to_str(module);
+    let logger = log::logger();
+    let metadata = log::Metadata::builder()
+        .level(level)
+        .target(&module)
+        .build();
+
+    if level > log::max_level() || !logger.enabled(&metadata) {
+        return;
+    }
+
+    let message = to_str(message);
+    let file = to_str(file);
+    let pairs = if n_pairs == 0 {
+        Vec::new()
+    } else {
+        let keys = slice::from_raw_parts(keys, n_pairs);
+        let values = slice::from_raw_parts(values, n_pairs);
+        keys.iter()
+            .zip(values.iter())
+            .map(|(k, v)| (to_str(*k), to_str(*v)))
+            .collect()
+    };
+    let pairs = pairs
+        .iter()
+        .map(|(k, v)| (k.as_ref(), v.as_ref()))
+        .collect::<Vec<_>>();
+
+    logger.log(
+        &Record::builder()
+            .metadata(metadata)
+            .args(format_args!("{}", message))
+            .module_path(Some(&module))
+            .file(if file.is_empty() { None } else { Some(&file) })
+            .line(if line > 0 { Some(line as u32) } else { None })
+            .key_values(&pairs.as_slice())
+            .build(),
+    );
+}
+
+// Install the logger by calling `Jlrs.installlogger`, messages below `filter` are discarded by
+// Julia. This takes three slots on the GC stack.
+pub(crate) fn install_logger<'frame, F>(frame: &mut F, filter: LevelFilter) -> JlrsResult<()>
+where
+    F: Frame<'frame>,
+{
+    unsafe {
+        let global = Global::new();
+        let log_fn = Value::new(frame, log_record as *mut c_void)?;
+        let min_level = Value::new(frame, min_level(filter))?;
+
+        Module::main(global)
+            .submodule("Jlrs")?
+            .function("installlogger")?
+            .call2(frame, log_fn, min_level)?
+            .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+        Ok(())
+    }
+}
@@ -1,9 +1,7 @@
 //! Runtime modes.
 
-use crate::frame::FrameIdx;
 use jl_sys::jl_get_ptls_states;
 use std::ffi::c_void;
-use std::ptr::null_mut;
 
 /// Mode used by the synchronous runtime.
 pub enum Sync {}
@@ -15,48 +13,25 @@ pub enum Async {}
 /// This trait is used to allow pushing and popping GC frames to behave differently in the two
 /// available modes.
 pub unsafe trait Mode: private::Sealed {
+    // `base` points to the first segment of the stack, `frame` to the header of a frame.
     #[doc(hidden)]
-    unsafe fn new_frame(stack: &mut [*mut c_void], size: usize, capacity: usize) -> FrameIdx;
+    unsafe fn push_frame(base: *mut *mut c_void, frame: *mut *mut c_void);
     #[doc(hidden)]
-    unsafe fn new_dynamic_frame(stack: &mut [*mut c_void], size: usize) -> FrameIdx;
-    #[doc(hidden)]
-    unsafe fn pop_frame(stack: &mut [*mut c_void], idx: FrameIdx);
+    unsafe fn pop_frame(base: *mut *mut c_void, frame: *mut *mut c_void);
 }
 
 unsafe impl Mode for Sync {
     #[inline(always)]
-    unsafe fn new_frame(stack: &mut [*mut c_void], size: usize, capacity: usize) -> FrameIdx {
-        let rtls = &mut *jl_get_ptls_states();
-        stack[size] = (capacity << 1) as _;
-        stack[size + 1] = rtls.pgcstack.cast();
-
-        for i in 0..capacity {
-            stack[size + 2 + i] = null_mut();
-        }
-
-        rtls.pgcstack = stack[size..].as_mut_ptr().cast();
-        stack[0] = (size + capacity + 2) as _;
-
-        FrameIdx(size + 2)
-    }
-
-    #[inline(always)]
-    unsafe fn new_dynamic_frame(stack: &mut [*mut c_void], size: usize) -> FrameIdx {
+    unsafe fn push_frame(_: *mut *mut c_void, frame: *mut *mut c_void) {
         let rtls = &mut *jl_get_ptls_states();
-        stack[size] = 0 as _;
-        stack[size + 1] = rtls.pgcstack.cast();
-
-        rtls.pgcstack = stack[size..].as_mut_ptr().cast();
-        stack[0] = (size + 2) as _;
-
-        FrameIdx(size + 2)
+        *frame.add(1) = rtls.pgcstack.cast();
+        rtls.pgcstack = frame.cast();
     }
 
     #[inline(always)]
-    unsafe fn pop_frame(stack: &mut [*mut c_void], idx: FrameIdx) {
+    unsafe fn pop_frame(_: *mut *mut c_void, frame: *mut *mut c_void) {
         let rtls = &mut *jl_get_ptls_states();
-        rtls.pgcstack = (&*rtls.pgcstack).prev;
-        stack[0] = (idx.0 - 2) as _;
+        rtls.pgcstack = (*frame.add(1)).cast();
     }
 }
 
@@ -92,37 +67,19 @@ unsafe impl Mode for Async {
     //     [3, 0, p1, 0...],
     //     ...
     // ]
+    //
+    // The frame with no slots is stored in the first segment of each stack, frames that are
+    // pushed to other segments are chained in the same way.
 
     #[inline(always)]
-    unsafe fn new_frame(stack: &mut [*mut c_void], size: usize, capacity: usize) -> FrameIdx {
-        stack[size] = (capacity << 1) as _;
-        stack[size + 1] = stack[2];
-
-        for i in 0..capacity {
-            stack[size + 2 + i] = null_mut();
-        }
-
-        stack[2] = stack[size..].as_mut_ptr().cast();
-        stack[0] = (size + capacity + 2) as _;
-
-        FrameIdx(size + 2)
-    }
-
-    #[inline(always)]
-    unsafe fn new_dynamic_frame(stack: &mut [*mut c_void], size: usize) -> FrameIdx {
-        stack[size] = 0 as _;
-        stack[size + 1] = stack[2];
-
-        stack[2] = stack[size..].as_mut_ptr().cast();
-        stack[0] = (size + 2) as _;
-
-        FrameIdx(size + 2)
+    unsafe fn push_frame(base: *mut *mut c_void, frame: *mut *mut c_void) {
+        *frame.add(1) = *base.add(2);
+        *base.add(2) = frame.cast();
     }
 
     #[inline(always)]
-    unsafe fn pop_frame(stack: &mut [*mut c_void], idx: FrameIdx) {
-        stack[0] = (idx.0 - 2) as _;
-        stack[2] = stack[idx.0 - 1];
+    unsafe fn pop_frame(base: *mut *mut c_void, frame: *mut *mut c_void) {
+        *base.add(2) = *frame.add(1);
     }
 }
 
@@ -13,13 +13,19 @@
 use crate::error::other_err;
 use crate::error::{JlrsError, JlrsResult};
 use crate::frame::AsyncFrame;
+use crate::gc::GcCollection;
 use crate::global::Global;
+#[cfg(feature = "log")]
+use crate::logging;
 use crate::mode::Async;
+use crate::redirect::{self, SharedBuffer, Sink, Sinks, Stream};
 use crate::stack::multitask::{MultitaskStack, TaskStack};
-use crate::stack::{Dynamic, StackView};
+use crate::stack::{Dynamic, RawStack, StackView};
 use crate::traits::multitask::{JuliaTask, ReturnChannel};
+use crate::traits::Frame;
 use crate::value::module::Module;
-use crate::value::Value;
+use crate::value::{CallResult, Value};
+use crate::version::check_version;
 use crate::INIT;
 use async_std::future::timeout;
 use async_std::sync::{
@@ -27,9 +33,14 @@ use async_std::sync::{
     RecvError, Sender as AsyncStdSender, TrySendError,
 };
 use async_std::task::{self, JoinHandle as AsyncStdHandle};
-use jl_sys::{jl_atexit_hook, jl_gc_safepoint, jl_init_with_image__threading, jl_is_initialized};
+use jl_sys::{
+    jl_atexit_hook, jl_gc_collect, jl_gc_enable, jl_gc_safepoint, jl_init_with_image__threading,
+    jl_is_initialized,
+};
+#[cfg(feature = "log")]
+use log::LevelFilter;
 use std::ffi::c_void;
-use std::io::{Error as IOError, ErrorKind};
+use std::io::{Error as IOError, ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Condvar, Mutex};
@@ -51,8 +62,9 @@ use std::time::Duration;
 ///  - `n_threads`: the number of threads that can be used to run tasks at the same time, it must
 ///    be less than the number of threads set with the `JULIA_NUM_THREADS` environment variable
 ///    (which defaults to 1).
-///  - `stack_size`: the size of a stack that is created for each of the tasks threads and the
-///    main thread (so `n_thread + 1` stacks with `stack_size` slots are created).
+///  - `stack_size`: the initial size of a stack that is created for each of the tasks threads
+///    and the main thread (so `n_thread + 1` stacks with `stack_size` slots are created). These
+///    stacks grow when they run out of slots.
 ///  - `process_events_ms`: to ensure the garbage collector can run and tasks that have yielded in
 ///    Julia are rescheduled, events must be processed periodically when at least one task is
 ///    running.
@@ -307,6 +319,294 @@ where
             })
     }
 
+    /// Parse and evaluate `code` in the module returned by `module`. If the code contains
+    /// multiple expressions, all of them are evaluated. The result of the last expression, or the
+    /// exception if one is thrown, is passed to `func` together with the frame it's rooted in.
+    /// Both closures are called on the thread Julia runs on. This method waits until `func` has
+    /// returned, and returns its result or an error if `module` returns one.
+    pub async fn eval_string<U, M, C, F>(&self, module: M, code: C, func: F) -> JlrsResult<U>
+    where
+        U: Send + 'static,
+        M: for<'base> FnOnce(Global<'base>) -> JlrsResult<Module<'base>> + Send + 'static,
+        C: Into<String>,
+        F: for<'base> FnOnce(
+                Global<'base>,
+                &mut AsyncFrame<'base>,
+                CallResult<'base, 'static>,
+            ) -> JlrsResult<U>
+            + Send
+            + 'static,
+    {
+        self.include_string(module, code, "string", func).await
+    }
+
+    /// Parse and evaluate `code` in the module returned by `module`, see
+    /// [`AsyncJulia::eval_string`]. This method waits until `func` has returned, and returns its
+    /// result or an error if the channel is full or `module` returns one.
+    ///
+    /// [`AsyncJulia::eval_string`]: struct.AsyncJulia.html#method.eval_string
+    pub fn try_eval_string<U, M, C, F>(&self, module: M, code: C, func: F) -> JlrsResult<U>
+    where
+        U: Send + 'static,
+        M: for<'base> FnOnce(Global<'base>) -> JlrsResult<Module<'base>> + Send + 'static,
+        C: Into<String>,
+        F: for<'base> FnOnce(
+                Global<'base>,
+                &mut AsyncFrame<'base>,
+                CallResult<'base, 'static>,
+            ) -> JlrsResult<U>
+            + Send
+            + 'static,
+    {
+        self.try_include_string(module, code, "string", func)
+    }
+
+    /// Parse and evaluate `code` in the module returned by `module`, `filename` is used as the
+    /// name of the file in backtraces and error messages. The result of the last expression, or
+    /// the exception if one is thrown, is passed to `func` together with the frame it's rooted
+    /// in. Both closures are called on the thread Julia runs on. This method waits until `func`
+    /// has returned, and returns its result or an error if `module` returns one.
+    pub async fn include_string<U, M, C, N, F>(
+        &self,
+        module: M,
+        code: C,
+        filename: N,
+        func: F,
+    ) -> JlrsResult<U>
+    where
+        U: Send + 'static,
+        M: for<'base> FnOnce(Global<'base>) -> JlrsResult<Module<'base>> + Send + 'static,
+        C: Into<String>,
+        N: Into<String>,
+        F: for<'base> FnOnce(
+                Global<'base>,
+                &mut AsyncFrame<'base>,
+                CallResult<'base, 'static>,
+            ) -> JlrsResult<U>
+            + Send
+            + 'static,
+    {
+        let (code, filename) = (code.into(), filename.into());
+        self.call(move |stack, _| call_include_string(stack, module, code, filename, func))
+            .await
+    }
+
+    /// Parse and evaluate `code` in the module returned by `module`, see
+    /// [`AsyncJulia::include_string`]. This method waits until `func` has returned, and returns
+    /// its result or an error if the channel is full or `module` returns one.
+    ///
+    /// [`AsyncJulia::include_string`]: struct.AsyncJulia.html#method.include_string
+    pub fn try_include_string<U, M, C, N, F>(
+        &self,
+        module: M,
+        code: C,
+        filename: N,
+        func: F,
+    ) -> JlrsResult<U>
+    where
+        U: Send + 'static,
+        M: for<'base> FnOnce(Global<'base>) -> JlrsResult<Module<'base>> + Send + 'static,
+        C: Into<String>,
+        N: Into<String>,
+        F: for<'base> FnOnce(
+                Global<'base>,
+                &mut AsyncFrame<'base>,
+                CallResult<'base, 'static>,
+            ) -> JlrsResult<U>
+            + Send
+            + 'static,
+    {
+        let (code, filename) = (code.into(), filename.into());
+        self.try_call(filename.clone(), move |stack, _| {
+            call_include_string(stack, module, code, filename, func)
+        })
+    }
+
+    // Send `func` to the runtime, which calls it on the main thread, and wait for its result.
+    async fn call<U, F>(&self, func: F) -> JlrsResult<U>
+    where
+        U: Send + 'static,
+        F: FnOnce(&mut RawStack, &mut Sinks) -> JlrsResult<U> + Send + 'static,
+    {
+        let (sender, receiver) = channel(1);
+        self.sender.send(Message::Call(new_job(func, sender))).await;
+        receiver.recv().await.map_err(|e| Box::new(other_err(e)))?
+    }
+
+    // Like `call`, but returns an error if the channel is full. The error contains `context`.
+    fn try_call<U, F>(&self, context: String, func: F) -> JlrsResult<U>
+    where
+        U: Send + 'static,
+        F: FnOnce(&mut RawStack, &mut Sinks) -> JlrsResult<U> + Send + 'static,
+    {
+        let (sender, receiver) = channel(1);
+        self.sender
+            .try_send(Message::Call(new_job(func, sender)))
+            .map_err(|e| match e {
+                TrySendError::Full(_) => Box::new(other_err(TrySendError::Full(context))),
+                TrySendError::Disconnected(_) => {
+                    Box::new(other_err(TrySendError::Disconnected(context)))
+                }
+            })?;
+
+        task::block_on(receiver.recv()).map_err(|e| Box::new(other_err(e)))?
+    }
+
+    /// Call `func` on the thread Julia runs on while Julia's `stdout` and `stderr` are
+    /// redirected, and return its result together with everything that has been written to these
+    /// streams in the meantime. No other messages are handled by the runtime until `func` has
+    /// returned, but tasks that are running on other threads can still print. Their output is
+    /// captured too. You must include `jlrs.jl` to use this method.
+    pub async fn capture_output<U, F>(&self, func: F) -> JlrsResult<(U, String)>
+    where
+        U: Send + 'static,
+        F: for<'base> FnOnce(Global<'base>, &mut AsyncFrame<'base>) -> JlrsResult<U>
+            + Send
+            + 'static,
+    {
+        self.call(move |stack, sinks| call_capture_output(stack, sinks, func))
+            .await
+    }
+
+    /// Call `func` while Julia's `stdout` and `stderr` are redirected, see
+    /// [`AsyncJulia::capture_output`]. It returns an error if the channel is full.
+    ///
+    /// [`AsyncJulia::capture_output`]: struct.AsyncJulia.html#method.capture_output
+    pub fn try_capture_output<U, F>(&self, func: F) -> JlrsResult<(U, String)>
+    where
+        U: Send + 'static,
+        F: for<'base> FnOnce(Global<'base>, &mut AsyncFrame<'base>) -> JlrsResult<U>
+            + Send
+            + 'static,
+    {
+        self.try_call(String::from("capture_output"), move |stack, sinks| {
+            call_capture_output(stack, sinks, func)
+        })
+    }
+
+    /// Redirect Julia's `stdout` to `sink`. This method waits until the redirection has been set
+    /// up. The redirection stays active until [`AsyncJulia::restore_stdout`] is called,
+    /// redirections can be nested. See the [`redirect`] module for more information. You must
+    /// include `jlrs.jl` to use this method.
+    ///
+    /// [`AsyncJulia::restore_stdout`]: struct.AsyncJulia.html#method.restore_stdout
+    /// [`redirect`]: ../redirect/index.html
+    pub async fn redirect_stdout<W: Write + Send + 'static>(&self, sink: W) -> JlrsResult<()> {
+        self.redirect(Stream::Stdout, Box::new(sink)).await
+    }
+
+    /// Redirect Julia's `stdout` to `sink`. This method waits until the redirection has been set
+    /// up. It returns an error if the channel is full.
+    pub fn try_redirect_stdout<W: Write + Send + 'static>(&self, sink: W) -> JlrsResult<()> {
+        self.try_redirect(Stream::Stdout, Box::new(sink))
+    }
+
+    /// Redirect Julia's `stderr` to `sink`. This method waits until the redirection has been set
+    /// up. The redirection stays active until [`AsyncJulia::restore_stderr`] is called,
+    /// redirections can be nested. See the [`redirect`] module for more information. You must
+    /// include `jlrs.jl` to use this method.
+    ///
+    /// [`AsyncJulia::restore_stderr`]: struct.AsyncJulia.html#method.restore_stderr
+    /// [`redirect`]: ../redirect/index.html
+    pub async fn redirect_stderr<W: Write + Send + 'static>(&self, sink: W) -> JlrsResult<()> {
+        self.redirect(Stream::Stderr, Box::new(sink)).await
+    }
+
+    /// Redirect Julia's `stderr` to `sink`. This method waits until the redirection has been set
+    /// up. It returns an error if the channel is full.
+    pub fn try_redirect_stderr<W: Write + Send + 'static>(&self, sink: W) -> JlrsResult<()> {
+        self.try_redirect(Stream::Stderr, Box::new(sink))
+    }
+
+    /// Undo the most recent redirection of `stdout`. All output that has been written before
+    /// the redirection is undone is written to the sink, which is flushed and dropped. Does
+    /// nothing if `stdout` isn't redirected.
+    pub async fn restore_stdout(&self) -> JlrsResult<()> {
+        self.restore(Stream::Stdout).await
+    }
+
+    /// Undo the most recent redirection of `stdout`. It returns an error if the channel is full.
+    pub fn try_restore_stdout(&self) -> JlrsResult<()> {
+        self.try_restore(Stream::Stdout)
+    }
+
+    /// Undo the most recent redirection of `stderr`. All output that has been written before
+    /// the redirection is undone is written to the sink, which is flushed and dropped. Does
+    /// nothing if `stderr` isn't redirected.
+    pub async fn restore_stderr(&self) -> JlrsResult<()> {
+        self.restore(Stream::Stderr).await
+    }
+
+    /// Undo the most recent redirection of `stderr`. It returns an error if the channel is full.
+    pub fn try_restore_stderr(&self) -> JlrsResult<()> {
+        self.try_restore(Stream::Stderr)
+    }
+
+    /// Install a logger in Julia that forwards messages logged with macros like `@info` to the
+    /// logger used by the `log` crate. This method waits until the logger has been installed.
+    /// Messages below `filter` are discarded by Julia, calling this method again replaces the
+    /// filter. See the [`logging`] module for more information. You must include `jlrs.jl` to
+    /// use this method. This method is only available if the `log` feature is enabled.
+    ///
+    /// [`logging`]: ../logging/index.html
+    #[cfg(feature = "log")]
+    pub async fn install_logger(&self, filter: LevelFilter) -> JlrsResult<()> {
+        self.call(move |stack, _| call_install_logger(stack, filter))
+            .await
+    }
+
+    /// Install a logger in Julia that forwards messages logged with macros like `@info` to the
+    /// logger used by the `log` crate. This method waits until the logger has been installed. It
+    /// returns an error if the channel is full. This method is only available if the `log`
+    /// feature is enabled.
+    #[cfg(feature = "log")]
+    pub fn try_install_logger(&self, filter: LevelFilter) -> JlrsResult<()> {
+        self.try_call(format!("{:?}", filter), move |stack, _| {
+            call_install_logger(stack, filter)
+        })
+    }
+
+    /// Force a collection of the given kind. This method waits until the collection has been
+    /// performed. Nothing happens if the GC is disabled. See the [`gc`] module for more
+    /// information.
+    ///
+    /// [`gc`]: ../gc/index.html
+    pub async fn gc_collect(&self, mode: GcCollection) -> JlrsResult<()> {
+        self.call(move |_, _| {
+            unsafe { jl_gc_collect(mode.as_jl_gc_collection()) };
+            Ok(())
+        })
+        .await
+    }
+
+    /// Force a collection of the given kind. This method waits until the collection has been
+    /// performed. It returns an error if the channel is full.
+    pub fn try_gc_collect(&self, mode: GcCollection) -> JlrsResult<()> {
+        self.try_call(format!("{:?}", mode), move |_, _| {
+            unsafe { jl_gc_collect(mode.as_jl_gc_collection()) };
+            Ok(())
+        })
+    }
+
+    /// Enable or disable the GC. This method waits until the state of the GC has been changed.
+    /// Tasks that are running can still change the state of the GC through their frame.
+    pub async fn enable_gc(&self, on: bool) -> JlrsResult<()> {
+        self.call(move |_, _| {
+            unsafe { jl_gc_enable(on as _) };
+            Ok(())
+        })
+        .await
+    }
+
+    /// Enable or disable the GC. This method waits until the state of the GC has been changed.
+    /// It returns an error if the channel is full.
+    pub fn try_enable_gc(&self, on: bool) -> JlrsResult<()> {
+        self.try_call(on.to_string(), move |_, _| {
+            unsafe { jl_gc_enable(on as _) };
+            Ok(())
+        })
+    }
+
     /// Returns the capacity of the channel.
     pub fn capacity(&self) -> usize {
         self.sender.capacity()
@@ -366,6 +666,28 @@ where
             (&mut *completed).as_jlrs_result()
         }
     }
+
+    async fn redirect(&self, stream: Stream, sink: Sink) -> JlrsResult<()> {
+        self.call(move |stack, sinks| call_redirect(stack, sinks, stream, sink))
+            .await
+    }
+
+    fn try_redirect(&self, stream: Stream, sink: Sink) -> JlrsResult<()> {
+        self.try_call(format!("{:?}", stream), move |stack, sinks| {
+            call_redirect(stack, sinks, stream, sink)
+        })
+    }
+
+    async fn restore(&self, stream: Stream) -> JlrsResult<()> {
+        self.call(move |stack, sinks| call_restore(stack, sinks, stream))
+            .await
+    }
+
+    fn try_restore(&self, stream: Stream) -> JlrsResult<()> {
+        self.try_call(format!("{:?}", stream), move |stack, sinks| {
+            call_restore(stack, sinks, stream)
+        })
+    }
 }
 
 enum Status {
@@ -398,6 +720,7 @@ enum Message<T, R> {
     ),
     Include(PathBuf, Arc<(AsyncStdMutex<Status>, AsyncStdCondvar)>),
     TryInclude(PathBuf, Arc<(Mutex<Status>, Condvar)>),
+    Call(Job),
     Complete(Wrapper, AsyncStdSender<Message<T, R>>),
     SetWakeFn(Arc<(AsyncStdMutex<Status>, AsyncStdCondvar)>),
     TrySetWakeFn(Arc<(Mutex<Status>, Condvar)>),
@@ -421,7 +744,7 @@ where
         task::spawn_local(async move {
             let mut tv = StackView::<Async, Dynamic>::new(&mut task_stack.raw);
 
-            match tv.new_frame() {
+            match tv.new_async_frame() {
                 Ok(frame_idx) => {
                     let global = Global::new();
                     let mut frame = AsyncFrame {
@@ -462,6 +785,8 @@ where
 {
     task::block_on(async {
         let mut mt_stack: MultitaskStack<T, R> = unsafe {
+            check_version()?;
+
             if jl_is_initialized() != 0 || INIT.swap(true, Ordering::SeqCst) {
                 return Err(JlrsError::AlreadyInitialized.into());
             }
@@ -470,6 +795,8 @@ where
             MultitaskStack::new(n_threads, stack_size)
         };
 
+        let mut sinks = Sinks::default();
+
         loop {
             match timeout(Duration::from_millis(process_events_ms), receiver.recv()).await {
                 Err(_) => unsafe {
@@ -505,6 +832,7 @@ where
                 Ok(Ok(Message::TryInclude(path, completed))) => {
                     try_include(&mut mt_stack.raw, path, completed)
                 }
+                Ok(Ok(Message::Call(job))) => call_job(&mut mt_stack.raw, &mut sinks, job),
                 Ok(Ok(Message::SetWakeFn(completed))) => {
                     set_wake_fn(&mut mt_stack.raw, completed).await
                 }
@@ -546,6 +874,8 @@ where
 {
     task::block_on(async {
         let mut mt_stack: MultitaskStack<T, R> = unsafe {
+            check_version()?;
+
             if jl_is_initialized() != 0 || INIT.swap(true, Ordering::SeqCst) {
                 return Err(JlrsError::AlreadyInitialized.into());
             }
@@ -570,6 +900,8 @@ where
             MultitaskStack::new(n_threads, stack_size)
         };
 
+        let mut sinks = Sinks::default();
+
         loop {
             match timeout(Duration::from_millis(process_events_ms), receiver.recv()).await {
                 Err(_) => unsafe {
@@ -604,6 +936,7 @@ where
                 Ok(Ok(Message::TryInclude(path, completed))) => {
                     try_include(&mut mt_stack.raw, path, completed)
                 }
+                Ok(Ok(Message::Call(job))) => call_job(&mut mt_stack.raw, &mut sinks, job),
                 Ok(Ok(Message::SetWakeFn(completed))) => {
                     set_wake_fn(&mut mt_stack.raw, completed).await
                 }
@@ -629,11 +962,11 @@ where
     })
 }
 
-fn call_set_wake_fn(stack: &mut [*mut c_void]) -> JlrsResult<()> {
+fn call_set_wake_fn(stack: &mut RawStack) -> JlrsResult<()> {
     unsafe {
         let global = Global::new();
         let mut view = StackView::<Async, Dynamic>::new(stack);
-        let idx = view.new_frame()?;
+        let idx = view.new_async_frame()?;
 
         let mut frame = AsyncFrame {
             idx,
@@ -694,11 +1027,11 @@ fn try_set_wake_fn(stacks: &mut [Option<TaskStack>], completed: Arc<(Mutex<Statu
     }
 }
 
-fn call_include(stack: &mut [*mut c_void], path: PathBuf) -> JlrsResult<()> {
+fn call_include(stack: &mut RawStack, path: PathBuf) -> JlrsResult<()> {
     unsafe {
         let global = Global::new();
         let mut view = StackView::<Async, Dynamic>::new(stack);
-        let idx = view.new_frame()?;
+        let idx = view.new_async_frame()?;
 
         let mut frame = AsyncFrame {
             idx,
@@ -774,3 +1107,160 @@ fn try_include(
         condvar.notify_one();
     }
 }
+
+// A function that is called on the main thread, it sends its result back to the caller.
+type Job = Box<dyn FnOnce(&mut RawStack, &mut Sinks) + Send>;
+
+fn new_job<U, F>(func: F, sender: AsyncStdSender<JlrsResult<U>>) -> Job
+where
+    U: Send + 'static,
+    F: FnOnce(&mut RawStack, &mut Sinks) -> JlrsResult<U> + Send + 'static,
+{
+    Box::new(move |stack, sinks| {
+        let res = func(stack, sinks);
+        // The channel has room for the result, sending only fails if the caller is gone.
+        sender.try_send(res).ok();
+    })
+}
+
+// Calls `job` with the GC stack of the main thread, this stack is always the last one.
+fn call_job(stacks: &mut [Option<TaskStack>], sinks: &mut Sinks, job: Job) {
+    let idx = stacks.len() - 1;
+    let mut stack = stacks[idx].take().expect("GC stack is corrupted.");
+    job(&mut stack.raw, sinks);
+    stacks[idx] = Some(stack);
+}
+
+fn call_include_string<T, M, F>(
+    stack: &mut RawStack,
+    module: M,
+    code: String,
+    filename: String,
+    func: F,
+) -> JlrsResult<T>
+where
+    M: for<'base> FnOnce(Global<'base>) -> JlrsResult<Module<'base>>,
+    F: for<'base> FnOnce(
+        Global<'base>,
+        &mut AsyncFrame<'base>,
+        CallResult<'base, 'static>,
+    ) -> JlrsResult<T>,
+{
+    unsafe {
+        let global = Global::new();
+        let mut view = StackView::<Async, Dynamic>::new(stack);
+        let idx = view.new_async_frame()?;
+
+        let mut frame = AsyncFrame {
+            idx,
+            len: 0,
+            memory: view,
+        };
+
+        let module = module(global)?;
+        let result = frame.include_string(module, code, filename)?;
+        func(global, &mut frame, result)
+    }
+}
+
+fn call_capture_output<U, F>(
+    stack: &mut RawStack,
+    sinks: &mut Sinks,
+    func: F,
+) -> JlrsResult<(U, String)>
+where
+    F: for<'base> FnOnce(Global<'base>, &mut AsyncFrame<'base>) -> JlrsResult<U>,
+{
+    let buffer = SharedBuffer::default();
+    call_redirect(stack, sinks, Stream::Stdout, Box::new(buffer.clone()))?;
+
+    if let Err(e) = call_redirect(stack, sinks, Stream::Stderr, Box::new(buffer.clone())) {
+        call_restore(stack, sinks, Stream::Stdout)?;
+        return Err(e);
+    }
+
+    let res = unsafe {
+        let global = Global::new();
+        let mut view = StackView::<Async, Dynamic>::new(stack);
+        view.new_async_frame().and_then(|idx| {
+            let mut frame = AsyncFrame {
+                idx,
+                len: 0,
+                memory: view,
+            };
+
+            func(global, &mut frame)
+        })
+    };
+
+    let restored_stderr = call_restore(stack, sinks, Stream::Stderr);
+    let restored_stdout = call_restore(stack, sinks, Stream::Stdout);
+    let res = res?;
+    restored_stderr?;
+    restored_stdout?;
+
+    Ok((res, buffer.to_string_lossy()))
+}
+
+fn call_redirect(
+    stack: &mut RawStack,
+    sinks: &mut Sinks,
+    stream: Stream,
+    sink: Sink,
+) -> JlrsResult<()> {
+    let mut sink = Box::new(sink);
+    unsafe {
+        let mut view = StackView::<Async, Dynamic>::new(stack);
+        let idx = view.new_async_frame()?;
+
+        let mut frame = AsyncFrame {
+            idx,
+            len: 0,
+            memory: view,
+        };
+
+        redirect::redirect(&mut frame, stream, &mut sink)?;
+    }
+
+    sinks.push(stream, sink);
+    Ok(())
+}
+
+fn call_restore(stack: &mut RawStack, sinks: &mut Sinks, stream: Stream) -> JlrsResult<()> {
+    let restored = unsafe {
+        let mut view = StackView::<Async, Dynamic>::new(stack);
+        let idx = view.new_async_frame()?;
+
+        let mut frame = AsyncFrame {
+            idx,
+            len: 0,
+            memory: view,
+        };
+
+        redirect::restore(&mut frame, stream)?
+    };
+
+    if restored {
+        if let Some(mut sink) = sinks.pop(stream) {
+            sink.flush().map_err(JlrsError::other)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "log")]
+fn call_install_logger(stack: &mut RawStack, filter: LevelFilter) -> JlrsResult<()> {
+    unsafe {
+        let mut view = StackView::<Async, Dynamic>::new(stack);
+        let idx = view.new_async_frame()?;
+
+        let mut frame = AsyncFrame {
+            idx,
+            len: 0,
+            memory: view,
+        };
+
+        logging::install_logger(&mut frame, filter)
+    }
+}
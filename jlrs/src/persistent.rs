@@ -0,0 +1,144 @@
+//! Keep values alive independently of frames.
+//!
+//! Every [`Value`] is tied to the lifetime of a frame, it can't be used after that frame has
+//! been dropped because the garbage collector is free to collect it. A [`Persistent`] keeps a
+//! value alive by storing it in a global root set that is managed by jlrs, a `Vector{Any}` in the
+//! `Jlrs` module, until the last handle to it is dropped. Every call to [`Persistent::new`] uses
+//! its own slot in this vector, so a value can be rooted several times. The value can be accessed
+//! again in any frame, which makes it possible to reuse an expensive object across calls to
+//! [`Julia::frame`] or between tasks that are executed by the async runtime.
+//!
+//! Dropping a `Persistent` doesn't call into Julia, because it can be dropped from any thread.
+//! Instead, the value is released the next time a `Persistent` is created or accessed, or when
+//! [`Persistent::release_dropped`] is called. You must include `jlrs.jl` to use this module.
+//!
+//! [`Value`]: ../value/struct.Value.html
+//! [`Persistent`]: struct.Persistent.html
+//! [`Persistent::new`]: struct.Persistent.html#method.new
+//! [`Persistent::release_dropped`]: struct.Persistent.html#method.release_dropped
+//! [`Julia::frame`]: ../struct.Julia.html#method.frame
+
+use crate::error::JlrsResult;
+use crate::global::Global;
+use crate::traits::{Cast, Frame};
+use crate::value::array::Array;
+use crate::value::module::Module;
+use crate::value::Value;
+use jl_sys::{jl_array_ptr_1d_push, jl_array_t, jl_arrayset, jl_nothing, jl_value_t};
+use std::sync::{Arc, Mutex};
+
+// The slots of the root set whose handles have been dropped, and the slots that can be reused.
+static DROPPED: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+static FREE: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+struct Root {
+    value: *mut jl_value_t,
+    slot: usize,
+}
+
+// Safety: the pointer is only dereferenced by Julia, through a frame. Dropping a root only
+// schedules its slot to be released.
+unsafe impl Send for Root {}
+unsafe impl Sync for Root {}
+
+impl Drop for Root {
+    fn drop(&mut self) {
+        DROPPED.lock().expect("Cannot lock").push(self.slot);
+    }
+}
+
+/// A handle to a value that is kept alive until the last handle to it is dropped. Cloning a
+/// `Persistent` is cheap and doesn't call into Julia. See the [`persistent`] module for more
+/// information.
+///
+/// [`persistent`]: index.html
+#[derive(Clone)]
+pub struct Persistent(Arc<Root>);
+
+impl Persistent {
+    /// Root `value` so it can be used after the frame it was created in has been dropped.
+    /// Returns an error if `Jlrs.persistent` can't be found.
+    pub fn new<'frame, F>(frame: &mut F, value: Value<'frame, 'static>) -> JlrsResult<Self>
+    where
+        F: Frame<'frame>,
+    {
+        Self::release_dropped(frame)?;
+
+        unsafe {
+            let roots = root_set()?;
+            let slot = match FREE.lock().expect("Cannot lock").pop() {
+                Some(slot) => {
+                    jl_arrayset(roots, value.ptr(), slot);
+                    slot
+                }
+                None => {
+                    jl_array_ptr_1d_push(roots, value.ptr());
+                    Array::wrap(roots).dimensions().size() - 1
+                }
+            };
+
+            Ok(Persistent(Arc::new(Root {
+                value: value.ptr(),
+                slot,
+            })))
+        }
+    }
+
+    /// Returns the rooted value. This takes no space on the GC stack because the value is
+    /// already rooted, it's only assigned the lifetime of `frame`. Returns an error if values
+    /// that have been dropped can't be released.
+    pub fn value<'frame, F>(&self, frame: &mut F) -> JlrsResult<Value<'frame, 'static>>
+    where
+        F: Frame<'frame>,
+    {
+        Self::release_dropped(frame)?;
+        unsafe { Ok(Value::wrap(self.0.value)) }
+    }
+
+    /// Returns the rooted value cast to `T`. Returns an error if values that have been dropped
+    /// can't be released or if the conversion fails.
+    pub fn cast<'frame, T, F>(
+        &self,
+        frame: &mut F,
+    ) -> JlrsResult<<T as Cast<'frame, 'static>>::Output>
+    where
+        T: Cast<'frame, 'static>,
+        F: Frame<'frame>,
+    {
+        self.value(frame)?.cast::<T>()
+    }
+
+    /// Release all values whose last handle has been dropped. This happens automatically when
+    /// a `Persistent` is created or accessed. Returns an error if `Jlrs.persistent` can't be
+    /// found, in which case no values are released.
+    pub fn release_dropped<'frame, F>(_frame: &mut F) -> JlrsResult<()>
+    where
+        F: Frame<'frame>,
+    {
+        let mut dropped = DROPPED.lock().expect("Cannot lock");
+        if dropped.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            let roots = root_set()?;
+            let mut free = FREE.lock().expect("Cannot lock");
+            for slot in dropped.drain(..) {
+                jl_arrayset(roots, jl_nothing, slot);
+                free.push(slot);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Returns `Jlrs.persistent`, the vector that roots all persistent values.
+unsafe fn root_set() -> JlrsResult<*mut jl_array_t> {
+    let global = Global::new();
+    let roots = Module::main(global)
+        .submodule("Jlrs")?
+        .global("persistent")?
+        .cast::<Array>()?;
+    Ok(roots.ptr())
+}
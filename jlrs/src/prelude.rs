@@ -2,7 +2,9 @@
 
 pub use crate::error::{JlrsError, JlrsResult};
 pub use crate::frame::{DynamicFrame, NullFrame, StaticFrame};
+pub use crate::gc::{Gc, GcCollection};
 pub use crate::global::Global;
+pub use crate::persistent::Persistent;
 pub use crate::traits::{Frame, ValidLayout};
 pub use crate::value::array::{
     Array, ArrayData, CopiedArray, InlineArrayDataMut, TypedArray, ValueArrayDataMut,
@@ -13,7 +15,7 @@ pub use crate::value::string::JuliaString;
 pub use crate::value::symbol::Symbol;
 pub use crate::value::tuple::*;
 pub use crate::value::{Value, Values};
-pub use crate::{CCall, Julia};
+pub use crate::{CCall, Julia, JuliaBuilder};
 
 #[cfg(feature = "jlrs-derive")]
 pub use jlrs_derive::{IntoJulia, JuliaStruct};
@@ -0,0 +1,151 @@
+//! Redirect Julia's `stdout` and `stderr` to Rust.
+//!
+//! By default everything Julia prints, including the stack traces printed by
+//! [`Value::tracing_call`] and warnings, is written directly to the file descriptors of the
+//! process. With [`Julia::redirect_stdout`] and [`Julia::redirect_stderr`], or their counterparts
+//! on [`AsyncJulia`], this output is written to an arbitrary implementation of [`Write`] instead.
+//! [`Julia::capture_output`] and [`AsyncJulia::capture_output`] can be used to capture
+//! everything that is printed while a closure is called as a `String`.
+//!
+//! Redirection is built on top of Julia's pipes: the stream is redirected to a `Pipe`, and a
+//! Julia task reads from that pipe and passes the data it reads to the sink. Because this task
+//! can only run when Julia is scheduling tasks, data is written to the sink while Julia code is
+//! running or waiting; everything that has been printed is guaranteed to have been written to
+//! the sink when the redirection is undone. Redirections can be nested, when a redirection is
+//! undone the previous one is restored. You must include `jlrs.jl` to use redirection.
+//!
+//! [`Value::tracing_call`]: ../value/struct.Value.html#method.tracing_call
+//! [`Julia::redirect_stdout`]: ../struct.Julia.html#method.redirect_stdout
+//! [`Julia::redirect_stderr`]: ../struct.Julia.html#method.redirect_stderr
+//! [`Julia::capture_output`]: ../struct.Julia.html#method.capture_output
+//! [`AsyncJulia`]: ../multitask/struct.AsyncJulia.html
+//! [`AsyncJulia::capture_output`]: ../multitask/struct.AsyncJulia.html#method.capture_output
+//! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+
+use crate::error::{JlrsError, JlrsResult};
+use crate::global::Global;
+use crate::traits::Frame;
+use crate::value::module::Module;
+use crate::value::symbol::Symbol;
+use crate::value::Value;
+use std::ffi::c_void;
+use std::io::{self, Write};
+use std::slice;
+use std::sync::{Arc, Mutex};
+
+/// The output streams of Julia that can be redirected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    fn name(self) -> &'static str {
+        match self {
+            Stream::Stdout => "stdout",
+            Stream::Stderr => "stderr",
+        }
+    }
+}
+
+/// A boxed sink that Julia's output can be redirected to.
+pub type Sink = Box<dyn Write + Send>;
+
+// The sinks of all active redirections. The sinks are boxed a second time so the pointer that
+// is handed to Julia stays valid when the vectors grow.
+#[derive(Default)]
+pub(crate) struct Sinks {
+    stdout: Vec<Box<Sink>>,
+    stderr: Vec<Box<Sink>>,
+}
+
+impl Sinks {
+    pub(crate) fn push(&mut self, stream: Stream, sink: Box<Sink>) {
+        match stream {
+            Stream::Stdout => self.stdout.push(sink),
+            Stream::Stderr => self.stderr.push(sink),
+        }
+    }
+
+    pub(crate) fn pop(&mut self, stream: Stream) -> Option<Box<Sink>> {
+        match stream {
+            Stream::Stdout => self.stdout.pop(),
+            Stream::Stderr => self.stderr.pop(),
+        }
+    }
+}
+
+// A sink that appends everything that is written to a shared buffer, used to capture output.
+#[derive(Clone, Default)]
+pub(crate) struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    pub(crate) fn to_string_lossy(&self) -> String {
+        let buffer = self.0.lock().expect("Cannot lock");
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().expect("Cannot lock").extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+unsafe extern "C" fn write_sink(sink: *mut c_void, data: *const u8, len: usize) {
+    let sink = &mut *sink.cast::<Sink>();
+    // Julia can't handle this error, and printing it would write to the redirected stream.
+    let _ = sink.write_all(slice::from_raw_parts(data, len));
+}
+
+// Redirect `stream` to `sink` by calling `Jlrs.redirect`. This takes three slots on the GC
+// stack. The sink must not be dropped until the redirection has been undone.
+pub(crate) fn redirect<'frame, F>(
+    frame: &mut F,
+    stream: Stream,
+    sink: &mut Box<Sink>,
+) -> JlrsResult<()>
+where
+    F: Frame<'frame>,
+{
+    unsafe {
+        let global = Global::new();
+        let stream = Symbol::new(global, stream.name());
+        let write_fn = Value::new(frame, write_sink as *mut c_void)?;
+        let sink = Value::new(frame, (&mut **sink as *mut Sink).cast::<c_void>())?;
+
+        Module::main(global)
+            .submodule("Jlrs")?
+            .function("redirect")?
+            .call3(frame, stream.into(), write_fn, sink)?
+            .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+        Ok(())
+    }
+}
+
+// Undo the most recent redirection of `stream` by calling `Jlrs.restore`, all pending output is
+// written to its sink before this function returns. Returns `false` if `stream` isn't
+// redirected. This takes one slot on the GC stack.
+pub(crate) fn restore<'frame, F>(frame: &mut F, stream: Stream) -> JlrsResult<bool>
+where
+    F: Frame<'frame>,
+{
+    unsafe {
+        let global = Global::new();
+        let stream = Symbol::new(global, stream.name());
+
+        Module::main(global)
+            .submodule("Jlrs")?
+            .function("restore")?
+            .call1(frame, stream.into())?
+            .map_err(|e| JlrsError::Exception(e.type_name().into()))?
+            .cast::<bool>()
+    }
+}
@@ -5,22 +5,33 @@
 // that should not be freed.
 //
 // Rust doesn't really like dynamically sized types, and as far as I'm aware something like alloca
-// is unavailable. As a workaround jlrs creates a boxed array to contain these frames. A new frame
+// is unavailable. As a workaround jlrs creates boxed arrays to contain these frames. A new frame
 // is pushed when a frame is created, and is popped when the frame is dropped.
 //
+// The stack consists of one or more segments. When a frame doesn't fit in the remaining space of
+// the current segment it's pushed to the next one, which is allocated if it doesn't exist yet.
+// Segments are never moved or freed while the stack is in use, which means the pointers in the
+// linked list of GC frames, and the pointers to slots that are held by `Output`s, `Slot`s and
+// `Values`, remain valid when the stack grows. The first slot of each segment contains the number
+// of slots of that segment that are in use. If a maximum size has been set, the total size of all
+// segments can't exceed it.
+//
 // Compared to the possibilities of the macros, jlrs is a bit more flexible. For example, the
 // DynamicFrame dynamically grows its associated GC frame which is not possible in the C API and
 // Outputs allow you to protect the result of a function call until the output's frame is dropped.
-// My driving assumption is that when the GC runs, it can't make any assumptions about the
-// contents of the GC stack based on earlier runs. Dynamically growing the frame does not make
-// sense in C because alloca is used but there's no technical reason preventing such a feature
-// from existing in Rust. Similarly, thanks to lifetimes we can enforce that a value can't live
-// longer than its frame while C can offer no such guarantees.
+// When a DynamicFrame reaches the end of its segment it continues in an extension frame without
+// slots of its own at the start of the next segment, this extension frame is popped together
+// with the DynamicFrame. My driving assumption is that when the GC runs, it can't make any
+// assumptions about the contents of the GC stack based on earlier runs. Dynamically growing the
+// frame does not make sense in C because alloca is used but there's no technical reason
+// preventing such a feature from existing in Rust. Similarly, thanks to lifetimes we can enforce
+// that a value can't live longer than its frame while C can offer no such guarantees.
 
 use crate::error::{AllocError, JlrsResult};
-use crate::frame::{FrameIdx, Output};
+use crate::frame::{FrameIdx, FrameInfo, FrameKind, Output};
 use crate::mode::Mode;
 use crate::value::{Value, Values};
+use std::cmp;
 use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::ptr::null_mut;
@@ -28,45 +39,292 @@ use std::ptr::null_mut;
 pub(crate) enum Static {}
 pub(crate) enum Dynamic {}
 
-pub(crate) struct RawStack(Box<[*mut c_void]>);
+// Bookkeeping for a frame that has been pushed to the stack. This is used to grow dynamic frames
+// and to provide information about the frames.
+struct FrameRecord {
+    kind: FrameKind,
+    idx: FrameIdx,
+    // The most recent part of a dynamic frame, either the frame itself or its last extension.
+    tail: FrameIdx,
+    len: usize,
+    outputs: usize,
+}
+
+pub(crate) struct RawStack {
+    segments: Vec<Box<[*mut c_void]>>,
+    frames: Vec<FrameRecord>,
+    current: usize,
+    max_size: Option<usize>,
+}
 
 impl RawStack {
     pub(crate) unsafe fn new(stack_size: usize) -> Self {
-        debug_assert!(stack_size > 0);
-        let mut raw = vec![null_mut(); stack_size];
+        let mut raw = vec![null_mut(); cmp::max(stack_size, 2)];
         raw[0] = 1 as _;
-        let boxed = raw.into_boxed_slice();
-        RawStack(boxed)
+
+        RawStack {
+            segments: vec![raw.into_boxed_slice()],
+            frames: Vec::new(),
+            current: 0,
+            max_size: None,
+        }
     }
 
-    pub(crate) fn as_mut<'original: 'scope, 'scope>(
-        &'original mut self,
-    ) -> &'scope mut [*mut c_void] {
-        &mut self.0
+    pub(crate) unsafe fn with_max_size(stack_size: usize, max_size: Option<usize>) -> Self {
+        let mut stack = RawStack::new(stack_size);
+        stack.max_size = max_size;
+        stack
     }
 
+    // The total number of slots in all segments.
     pub(crate) fn size(&self) -> usize {
-        self.0.len()
+        self.segments.iter().map(|s| s.len()).sum()
+    }
+
+    pub(crate) fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
+
+    pub(crate) fn set_max_size(&mut self, max_size: Option<usize>) {
+        self.max_size = max_size;
+    }
+
+    fn used(&self, segment: usize) -> usize {
+        self.segments[segment][0] as usize
+    }
+
+    fn set_used(&mut self, segment: usize, used: usize) {
+        self.segments[segment][0] = used as _;
+    }
+
+    // The number of roots of the GC frame whose first slot is at `idx`.
+    fn n_roots(&self, idx: FrameIdx) -> usize {
+        self.segments[idx.segment][idx.offset - 2] as usize >> 1
+    }
+
+    // The frame that is currently on top of the stack.
+    fn top(&mut self) -> &mut FrameRecord {
+        self.frames
+            .last_mut()
+            .expect("The stack contains no frames")
+    }
+
+    // Returns the segment and offset of `n` consecutive free slots at the top of the stack. If
+    // the current segment doesn't have enough space left the next segment is used instead, a new
+    // one is allocated if that segment doesn't exist or is too small.
+    unsafe fn reserve(&mut self, n: usize) -> Result<(usize, usize), AllocError> {
+        let used = self.used(self.current);
+        if used + n <= self.segments[self.current].len() {
+            return Ok((self.current, used));
+        }
+
+        let next = self.current + 1;
+        if next >= self.segments.len() || self.segments[next].len() < n + 1 {
+            // Segments above the current one are unused.
+            self.segments.truncate(next);
+
+            let allocated = self.size();
+            let mut len = cmp::max(n + 1, allocated);
+            if let Some(max_size) = self.max_size {
+                if allocated + n + 1 > max_size {
+                    return Err(AllocError::StackOverflow(n, max_size));
+                }
+
+                len = cmp::min(len, max_size - allocated);
+            }
+
+            self.segments.push(vec![null_mut(); len].into_boxed_slice());
+        }
+
+        self.current = next;
+        self.set_used(next, 1);
+        Ok((next, 1))
+    }
+
+    // Push a new GC frame with `capacity` slots, `extra` additional slots are reserved after this
+    // frame.
+    unsafe fn push_gc_frame<M: Mode>(
+        &mut self,
+        capacity: usize,
+        extra: usize,
+    ) -> Result<FrameIdx, AllocError> {
+        let prev = self.current;
+        let (segment, offset) = self.reserve(capacity + extra + 2)?;
+
+        {
+            let raw = &mut self.segments[segment];
+            raw[offset] = (capacity << 1) as _;
+            for i in 0..capacity {
+                raw[offset + 2 + i] = null_mut();
+            }
+        }
+
+        self.set_used(segment, offset + capacity + 2);
+        let frame = self.segments[segment][offset..].as_mut_ptr();
+        M::push_frame(self.segments[0].as_mut_ptr(), frame);
+
+        Ok(FrameIdx {
+            segment,
+            offset: offset + 2,
+            prev,
+        })
+    }
+
+    unsafe fn push_frame<M: Mode>(
+        &mut self,
+        kind: FrameKind,
+        capacity: usize,
+    ) -> JlrsResult<FrameIdx> {
+        let idx = self
+            .push_gc_frame::<M>(capacity, 0)
+            .map_err(Into::<Box<_>>::into)?;
+
+        self.frames.push(FrameRecord {
+            kind,
+            idx,
+            tail: idx,
+            len: 0,
+            outputs: 0,
+        });
+
+        Ok(idx)
+    }
+
+    unsafe fn pop_frame<M: Mode>(&mut self, idx: FrameIdx) {
+        let frame = self.segments[idx.segment][idx.offset - 2..].as_mut_ptr();
+        M::pop_frame(self.segments[0].as_mut_ptr(), frame);
+        self.set_used(idx.segment, idx.offset - 2);
+        self.current = idx.prev;
+        self.frames.pop();
+    }
+
+    // Make sure `n` slots can be added to the dynamic frame on top of the stack. If there's not
+    // enough space left in the current segment, an extension frame is pushed to the next one.
+    unsafe fn extend<M: Mode>(&mut self, n: usize) -> Result<(), AllocError> {
+        let tail = self.top().tail;
+        if self.used(tail.segment) + n <= self.segments[tail.segment].len() {
+            return Ok(());
+        }
+
+        let tail = self.push_gc_frame::<M>(0, n)?;
+        self.top().tail = tail;
+        Ok(())
+    }
+
+    // Add a root to the dynamic frame on top of the stack. Returns a pointer to the new slot.
+    unsafe fn push_root<M: Mode>(
+        &mut self,
+        value: *mut c_void,
+    ) -> Result<*mut *mut c_void, AllocError> {
+        self.extend::<M>(1)?;
+
+        let top = self.top();
+        top.len += 1;
+        let tail = top.tail;
+
+        let used = self.used(tail.segment);
+        let raw = &mut self.segments[tail.segment];
+        raw[used] = value;
+        raw[tail.offset - 2] = (raw[tail.offset - 2] as usize + 2) as _;
+        raw[0] = (used + 1) as _;
+
+        Ok(raw[used..].as_mut_ptr())
+    }
+
+    fn slot(&mut self, idx: FrameIdx, offset: usize) -> *mut *mut c_void {
+        self.segments[idx.segment][idx.offset + offset..].as_mut_ptr()
+    }
+
+    // The values rooted by a frame, including the contents of its outputs.
+    fn roots(&self, record: &FrameRecord) -> Vec<*mut c_void> {
+        let idx = record.idx;
+        if let FrameKind::Static = record.kind {
+            return self.segments[idx.segment][idx.offset..idx.offset + record.len].to_vec();
+        }
+
+        // The extensions of a dynamic frame are pushed to the start of the following segments.
+        let mut roots = Vec::with_capacity(record.len);
+        for segment in idx.segment..=record.tail.segment {
+            let part = if segment == idx.segment {
+                idx
+            } else {
+                FrameIdx {
+                    segment,
+                    offset: 3,
+                    prev: segment - 1,
+                }
+            };
+
+            let n = self.n_roots(part);
+            roots.extend_from_slice(&self.segments[segment][part.offset..part.offset + n]);
+        }
+
+        roots
+    }
+
+    fn frame_info(&self, record: &FrameRecord, root_types: bool) -> FrameInfo {
+        let capacity = match record.kind {
+            FrameKind::Static => Some(self.n_roots(record.idx)),
+            _ => None,
+        };
+
+        let root_types = if root_types {
+            let types = self
+                .roots(record)
+                .into_iter()
+                .map(|root| {
+                    if root.is_null() {
+                        None
+                    } else {
+                        unsafe { Some(Value::wrap(root.cast()).type_name().to_string()) }
+                    }
+                })
+                .collect();
+
+            Some(types)
+        } else {
+            None
+        };
+
+        FrameInfo {
+            kind: record.kind,
+            capacity,
+            len: record.len,
+            outputs: record.outputs,
+            root_types,
+        }
     }
 }
 
 pub(crate) struct StackView<'stack, U: Mode, V> {
-    stack: &'stack mut [*mut c_void],
+    stack: &'stack mut RawStack,
     _u: PhantomData<U>,
     _v: PhantomData<V>,
 }
 
 impl<'stack, M: Mode, V> StackView<'stack, M, V> {
-    pub(crate) fn size(&self) -> usize {
-        self.stack[0] as _
+    // Returns information about the frames on this stack, starting with the one on top.
+    pub(crate) fn frame_chain(&self, root_types: bool) -> Vec<FrameInfo> {
+        self.stack
+            .frames
+            .iter()
+            .rev()
+            .map(|record| self.stack.frame_info(record, root_types))
+            .collect()
     }
 
-    pub(crate) fn print_memory(&self) {
-        println!("{:?}", &self.stack);
+    // Returns information about the frame on top of this stack.
+    pub(crate) fn frame_info(&self, root_types: bool) -> FrameInfo {
+        let record = self
+            .stack
+            .frames
+            .last()
+            .expect("The stack contains no frames");
+        self.stack.frame_info(record, root_types)
     }
 
     pub(crate) unsafe fn pop_frame(&mut self, idx: FrameIdx) {
-        M::pop_frame(&mut self.stack, idx)
+        self.stack.pop_frame::<M>(idx)
     }
 
     pub(crate) unsafe fn nest_static<'nested>(&'nested mut self) -> StackView<'nested, M, Static> {
@@ -87,14 +345,13 @@ impl<'stack, M: Mode, V> StackView<'stack, M, V> {
         }
     }
 
-    pub(crate) unsafe fn as_values<'output>(
+    pub(crate) unsafe fn protect_output<'output>(
         &mut self,
-        idx: FrameIdx,
-        offset: usize,
-        n: usize,
-    ) -> Values<'output> {
-        let ptr = self.stack[idx.0 + offset..].as_mut_ptr();
-        Values::wrap(ptr.cast(), n)
+        output: Output,
+        value: *mut c_void,
+    ) -> Value<'output, 'static> {
+        *output.slot = value;
+        Value::wrap(value.cast())
     }
 }
 
@@ -102,7 +359,7 @@ impl<'stack, M> StackView<'stack, M, Dynamic>
 where
     M: Mode,
 {
-    pub(crate) unsafe fn new(stack: &'stack mut [*mut c_void]) -> Self {
+    pub(crate) unsafe fn new(stack: &'stack mut RawStack) -> Self {
         StackView {
             stack,
             _u: PhantomData,
@@ -111,56 +368,55 @@ where
     }
 
     pub(crate) unsafe fn new_frame(&mut self) -> JlrsResult<FrameIdx> {
-        if self.size() + 2 >= self.stack.len() {
-            return Err(Box::new(
-                AllocError::StackOverflow(2, self.stack.len()).into(),
-            ));
-        }
-
-        let size = self.size();
-        Ok(M::new_dynamic_frame(&mut self.stack, size))
+        self.stack.push_frame::<M>(FrameKind::Dynamic, 0)
     }
 
-    pub(crate) unsafe fn new_output<'output>(
-        &mut self,
-        idx: FrameIdx,
-    ) -> JlrsResult<Output<'output>> {
-        if self.size() >= self.stack.len() {
-            return Err(Box::new(
-                AllocError::StackOverflow(1, self.stack.len()).into(),
-            ));
-        }
-
-        let sz = self.size();
-        self.stack[sz] = null_mut();
-        self.stack[idx.0 - 2] = (self.stack[idx.0 - 2] as usize + 2) as _;
-        self.stack[0] = (self.size() + 1) as _;
-        Ok(Output::new(sz))
+    pub(crate) unsafe fn new_output<'output>(&mut self) -> JlrsResult<Output<'output>> {
+        let slot = self
+            .stack
+            .push_root::<M>(null_mut())
+            .map_err(Into::<Box<_>>::into)?;
+        self.stack.top().outputs += 1;
+        Ok(Output::new(slot))
     }
 
     pub(crate) unsafe fn protect<'output>(
         &mut self,
-        idx: FrameIdx,
         value: *mut c_void,
     ) -> Result<Value<'output, 'static>, AllocError> {
-        if self.size() == self.stack.len() {
-            return Err(AllocError::StackOverflow(1, self.stack.len()));
-        }
+        self.stack.push_root::<M>(value)?;
+        Ok(Value::wrap(value.cast()))
+    }
 
-        self.stack[self.size()] = value.cast::<_>();
-        self.stack[idx.0 - 2] = (self.stack[idx.0 - 2] as usize + 2) as _;
-        self.stack[0] = (self.size() + 1) as _;
+    // Protect `n` values that are created by `values`, the values are stored in consecutive
+    // slots.
+    pub(crate) unsafe fn protect_many<'output, I>(
+        &mut self,
+        n: usize,
+        values: I,
+    ) -> Result<Values<'output>, AllocError>
+    where
+        I: IntoIterator<Item = *mut c_void>,
+    {
+        self.stack.extend::<M>(n)?;
+
+        let mut first = null_mut();
+        for (i, value) in values.into_iter().enumerate() {
+            let slot = self.stack.push_root::<M>(value)?;
+            if i == 0 {
+                first = slot;
+            }
+        }
 
-        Ok(Value::wrap(value.cast::<_>()))
+        Ok(Values::wrap(first.cast(), n))
     }
+}
 
-    pub(crate) unsafe fn protect_output<'output>(
-        &mut self,
-        output: Output,
-        value: *mut c_void,
-    ) -> Value<'output, 'static> {
-        self.stack[output.offset] = value.cast::<_>();
-        Value::wrap(value.cast::<_>())
+#[cfg(all(feature = "async", target_os = "linux"))]
+impl<'stack> StackView<'stack, crate::mode::Async, Dynamic> {
+    pub(crate) unsafe fn new_async_frame(&mut self) -> JlrsResult<FrameIdx> {
+        self.stack
+            .push_frame::<crate::mode::Async>(FrameKind::Async, 0)
     }
 }
 
@@ -168,7 +424,7 @@ impl<'stack, M> StackView<'stack, M, Static>
 where
     M: Mode,
 {
-    pub(crate) unsafe fn new(stack: &'stack mut [*mut c_void]) -> Self {
+    pub(crate) unsafe fn new(stack: &'stack mut RawStack) -> Self {
         StackView {
             stack,
             _u: PhantomData,
@@ -177,14 +433,7 @@ where
     }
 
     pub(crate) unsafe fn new_frame(&mut self, capacity: usize) -> JlrsResult<FrameIdx> {
-        let size = self.size();
-        if size + capacity + 2 >= self.stack.len() {
-            return Err(Box::new(
-                AllocError::StackOverflow(capacity + 2, self.stack.len()).into(),
-            ));
-        }
-
-        Ok(M::new_frame(&mut self.stack, size, capacity))
+        self.stack.push_frame::<M>(FrameKind::Static, capacity)
     }
 
     pub(crate) unsafe fn new_output<'output>(
@@ -192,7 +441,10 @@ where
         idx: FrameIdx,
         offset: usize,
     ) -> Output<'output> {
-        Output::new(idx.0 + offset)
+        let top = self.stack.top();
+        top.len = offset + 1;
+        top.outputs += 1;
+        Output::new(self.stack.slot(idx, offset))
     }
 
     pub(crate) unsafe fn protect<'output>(
@@ -201,20 +453,29 @@ where
         offset: usize,
         value: *mut c_void,
     ) -> Value<'output, 'static> {
-        self.stack[idx.0 + offset] = value;
+        self.stack.top().len = offset + 1;
+        *self.stack.slot(idx, offset) = value;
         Value::wrap(value.cast())
     }
+
+    pub(crate) unsafe fn as_values<'output>(
+        &mut self,
+        idx: FrameIdx,
+        offset: usize,
+        n: usize,
+    ) -> Values<'output> {
+        Values::wrap(self.stack.slot(idx, offset).cast(), n)
+    }
 }
 
 #[cfg(all(feature = "async", target_os = "linux"))]
 pub(crate) mod multitask {
+    use super::RawStack;
     use crate::error::{AllocError, JlrsError, JlrsResult};
     use crate::traits::multitask::JuliaTask;
     use async_std::task::JoinHandle;
     use jl_sys::jl_get_ptls_states;
     use std::collections::VecDeque;
-    use std::ffi::c_void;
-    use std::ptr::null_mut;
 
     struct Node<T> {
         value: T,
@@ -264,39 +525,33 @@ pub(crate) mod multitask {
     }
 
     pub(crate) struct TaskStack {
-        pub(crate) raw: Box<[*mut c_void]>,
+        pub(crate) raw: RawStack,
     }
 
     impl TaskStack {
         pub(crate) unsafe fn new(stack_size: usize) -> Self {
-            let v = vec![null_mut(); stack_size];
-
             Self {
-                raw: v.into_boxed_slice(),
+                raw: RawStack::new(stack_size),
             }
         }
 
         pub(crate) unsafe fn init(&mut self) -> JlrsResult<()> {
-            if self.raw.len() < 3 {
+            let base = &mut self.raw.segments[0];
+            if base.len() < 3 {
                 Err(JlrsError::AllocError(AllocError::StackOverflow(
                     3,
-                    self.raw.len(),
+                    base.len(),
                 )))?;
             }
 
             let rtls = &mut *jl_get_ptls_states();
 
-            self.raw[0] = 3 as _;
-            self.raw[2] = rtls.pgcstack as _;
+            base[0] = 3 as _;
+            base[2] = rtls.pgcstack as _;
 
-            rtls.pgcstack = self.raw[1..].as_mut_ptr().cast();
+            rtls.pgcstack = base[1..].as_mut_ptr().cast();
             Ok(())
         }
-
-        #[allow(dead_code)]
-        pub fn print_memory(&self) {
-            println!("{:?}", self.raw.as_ref());
-        }
     }
 
     pub(crate) struct MultitaskStack<T, R> {
@@ -357,15 +612,5 @@ pub(crate) mod multitask {
         pub(crate) fn pop_pending(&mut self) -> Option<Box<dyn JuliaTask<T = T, R = R>>> {
             self.queue.pop_front()
         }
-
-        // keep this around for debugging purposes
-        #[allow(dead_code)]
-        pub(crate) fn print_memory(&self) {
-            println!("[");
-            for stack in self.raw.iter() {
-                stack.as_ref().map(|f| f.print_memory());
-            }
-            println!("]");
-        }
     }
 }
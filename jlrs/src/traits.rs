@@ -37,23 +37,30 @@ pub mod multitask;
 use crate::error::{AllocError, JlrsError, JlrsResult};
 #[cfg(all(feature = "async", target_os = "linux"))]
 use crate::frame::AsyncFrame;
-use crate::frame::{DynamicFrame, NullFrame, Output, StaticFrame};
+use crate::frame::{DynamicFrame, FrameInfo, NullFrame, Output, Slot, StaticFrame};
+use crate::global::Global;
 #[cfg(all(feature = "async", target_os = "linux"))]
 use crate::mode::Async;
 use crate::mode::{Mode, Sync};
+use crate::value::array::Array;
+use crate::value::collections::{inline_element, pointer_element, with_boxed_element, with_root};
 use crate::value::datatype::DataType;
+use crate::value::missing::Missing;
+use crate::value::module::Module;
+use crate::value::named_tuple::NamedTuple;
 use crate::value::string::JuliaString;
 use crate::value::symbol::Symbol;
-use crate::value::Value;
+use crate::value::{CallResult, Value};
 use jl_sys::{
     jl_bool_type, jl_box_bool, jl_box_char, jl_box_float32, jl_box_float64, jl_box_int16,
     jl_box_int32, jl_box_int64, jl_box_int8, jl_box_uint16, jl_box_uint32, jl_box_uint64,
-    jl_box_uint8, jl_box_voidpointer, jl_char_type, jl_datatype_t, jl_float32_type,
-    jl_float64_type, jl_int16_type, jl_int32_type, jl_int64_type, jl_int8_type, jl_pchar_to_string,
-    jl_string_data, jl_string_len, jl_uint16_type, jl_uint32_type, jl_uint64_type, jl_uint8_type,
-    jl_unbox_float32, jl_unbox_float64, jl_unbox_int16, jl_unbox_int32, jl_unbox_int64,
-    jl_unbox_int8, jl_unbox_uint16, jl_unbox_uint32, jl_unbox_uint64, jl_unbox_uint8,
-    jl_unbox_voidpointer, jl_value_t, jl_voidpointer_type,
+    jl_box_uint8, jl_box_voidpointer, jl_char_type, jl_datatype_t, jl_fieldref, jl_float32_type,
+    jl_float64_type, jl_int16_type, jl_int32_type, jl_int64_type, jl_int8_type, jl_nothing,
+    jl_nothing_type, jl_pchar_to_string, jl_string_data, jl_string_len, jl_string_type,
+    jl_type_union, jl_uint16_type, jl_uint32_type, jl_uint64_type, jl_uint8_type, jl_unbox_float32,
+    jl_unbox_float64, jl_unbox_int16, jl_unbox_int32, jl_unbox_int64, jl_unbox_int8,
+    jl_unbox_uint16, jl_unbox_uint32, jl_unbox_uint64, jl_unbox_uint8, jl_unbox_voidpointer,
+    jl_value_t, jl_voidpointer_type,
 };
 use std::borrow::Cow;
 use std::ffi::c_void;
@@ -72,6 +79,24 @@ macro_rules! p {
 /// [`Symbol`]: ../value/symbol/struct.Symbol.html
 pub unsafe trait TemporarySymbol: private::TemporarySymbol {}
 
+/// Trait implemented by types that can be used as the keyword arguments of a function call, eg
+/// with [`Value::call_keywords`]. It's implemented for slices, arrays and vectors of
+/// `(Symbol, Value)` pairs, for [`NamedTuple`], and for `Value`. If a `Value` is a `NamedTuple`
+/// it's used as is, otherwise its fields are used as the keyword arguments. This means an
+/// instance of a struct that derives `IntoJulia` can be used as the keyword arguments of a
+/// function call after converting it to a `Value` with [`Value::new`].
+///
+/// [`Value::call_keywords`]: ../value/struct.Value.html#method.call_keywords
+/// [`Value::new`]: ../value/struct.Value.html#method.new
+/// [`NamedTuple`]: ../value/named_tuple/struct.NamedTuple.html
+///
+/// # Safety
+///
+/// The keyword arguments must be converted to a valid `NamedTuple` that's rooted in the frame
+/// that's used to convert them, or that's rooted elsewhere for at least as long as the call
+/// takes. This trait is sealed, it can't be implemented outside jlrs.
+pub unsafe trait Keywords<'borrow>: private::Keywords<'borrow> {}
+
 /// Trait implemented as part of `JuliaStruct` that is used to verify this type has the same
 /// layout as the Julia value.
 pub unsafe trait ValidLayout {
@@ -140,6 +165,28 @@ pub unsafe trait JuliaType {
     unsafe fn julia_type() -> *mut jl_datatype_t;
 }
 
+/// Trait implemented by types that can be used as the elements of a collection that's converted
+/// to Julia with [`Value::new`], [`Value::new_dict`] or [`Value::new_set`], it provides the
+/// element type of the new collection. This trait is implemented for all types that implement
+/// [`JuliaType`], strings, `Option`s, and vectors: a `Vec<Option<String>>` is converted to a
+/// `Vector{Union{Nothing, String}}`.
+///
+/// # Safety
+///
+/// `element_type` must return a valid Julia type. This type is not rooted, so the caller must
+/// root it before anything else is allocated unless it's known to be cached by Julia, like the
+/// types of primitives and applied types are. Implementations that create intermediate types,
+/// eg the element type of a `Vec<Option<T>>`, must keep them rooted while they're used.
+///
+/// [`Value::new`]: ../value/struct.Value.html#method.new
+/// [`Value::new_dict`]: ../value/struct.Value.html#method.new_dict
+/// [`Value::new_set`]: ../value/struct.Value.html#method.new_set
+/// [`JuliaType`]: trait.JuliaType.html
+pub unsafe trait ElementType {
+    #[doc(hidden)]
+    unsafe fn element_type() -> *mut jl_value_t;
+}
+
 /// This trait can be derived in order to provide a mapping between a type in Julia and one in
 /// Rust. When this trait is derived, the following traits are implemented:
 ///
@@ -270,7 +317,9 @@ impl_julia_typecheck!(char);
 impl_julia_typecheck!(*mut c_void);
 
 /// This trait is implemented by types that a [`Value`] can be converted into by calling
-/// [`Value::cast`]. This includes types like `String`, [`Array`], and `u8`.
+/// [`Value::cast`]. This includes types like `String`, [`Array`], and `u8`. Tuples are cast
+/// element-wise into Rust tuples of castable types, a `Value` can be used for elements that
+/// aren't stored inline.
 ///
 /// [`Value`]: ../value/struct.Value.html
 /// [`Value::cast`]: ../value/struct.Value.html#method.cast
@@ -282,6 +331,48 @@ pub unsafe trait Cast<'frame, 'data> {
 
     #[doc(hidden)]
     unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output;
+
+    // Cast the field at `idx` of `value`. A field that is stored inline is boxed first, so this
+    // must be overridden by types whose output references the value it has been cast from.
+    #[doc(hidden)]
+    fn cast_field(value: Value<'frame, 'data>, idx: usize) -> JlrsResult<Self::Output> {
+        if idx >= value.n_fields() {
+            Err(JlrsError::OutOfBounds(idx, value.n_fields()))?
+        }
+
+        unsafe { Self::cast(Value::wrap(jl_fieldref(value.ptr(), idx))) }
+    }
+
+    // Cast the field at `idx` of `value` without checking if this is valid, `cast_field` must
+    // succeed for the same arguments. This must be overridden by the same types as `cast_field`.
+    #[doc(hidden)]
+    unsafe fn cast_field_unchecked(value: Value<'frame, 'data>, idx: usize) -> Self::Output {
+        Self::cast_unchecked(Value::wrap(jl_fieldref(value.ptr(), idx)))
+    }
+
+    // Cast the element at `idx` of `array`, which must be in bounds. An element that is stored
+    // inline is boxed first and the box is rooted while it's cast, so this must be overridden by
+    // types whose output references the value it has been cast from.
+    #[doc(hidden)]
+    unsafe fn cast_element(array: Array<'frame, 'data>, idx: usize) -> JlrsResult<Self::Output> {
+        if array.is_value_array() {
+            Self::cast(Value::wrap(pointer_element(array, idx)))
+        } else {
+            with_boxed_element(array, idx, Self::cast)
+        }
+    }
+
+    // Cast the element at `idx` of `array` without checking if this is valid, `cast_element` must
+    // succeed for the same arguments. This must be overridden by the same types as
+    // `cast_element`.
+    #[doc(hidden)]
+    unsafe fn cast_element_unchecked(array: Array<'frame, 'data>, idx: usize) -> Self::Output {
+        if array.is_value_array() {
+            Self::cast_unchecked(Value::wrap(pointer_element(array, idx)))
+        } else {
+            with_boxed_element(array, idx, |element| Self::cast_unchecked(element))
+        }
+    }
 }
 
 /// Functionality shared by [`StaticFrame`] and [`DynamicFrame`]. These structs let you protect
@@ -332,14 +423,77 @@ pub trait Frame<'frame>: private::Frame<'frame> {
     /// Returns an error if there is not enough space on the stack.
     fn output(&mut self) -> JlrsResult<Output<'frame>>;
 
+    /// Returns a new `Slot`, this takes one slot on the GC stack. Unlike an `Output`, a `Slot`
+    /// can be reused: every time a value is assigned to it the previous value is no longer
+    /// protected by it. This can be used to keep the working set of a loop in a fixed number of
+    /// slots, rather than creating a nested frame for every iteration.
+    ///
+    /// Returns an error if there is not enough space on the stack.
+    fn reserve_slot(&mut self) -> JlrsResult<Slot<'frame>> {
+        let output = self.output()?;
+        unsafe { Ok(Slot::new(output.slot)) }
+    }
+
     /// Returns the number of values belonging to this frame.
     fn size(&self) -> usize;
 
-    #[doc(hidden)]
-    // Exists for debugging purposes, prints the contents of the GC stack.
-    fn print_memory(&self);
+    /// Parse and evaluate `code` in `module` by calling `Base.include_string`. If the code
+    /// contains multiple expressions, all of them are evaluated and the result of the last one
+    /// is returned. This takes three slots on the GC stack. Returns the result if no exception
+    /// is thrown, the exception if one is, or an error if no space is left on the stack. Like
+    /// `include`, `Base.include_string` wraps exceptions in a `LoadError`, the original
+    /// exception is its `error` field.
+    fn eval_string<'base, C>(
+        &mut self,
+        module: Module<'base>,
+        code: C,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        Self: Sized,
+        C: AsRef<str>,
+    {
+        self.include_string(module, code, "string")
+    }
+
+    /// Parse and evaluate `code` in `module` by calling `Base.include_string`, `filename` is
+    /// used as the name of the file in backtraces and error messages. This takes three slots on
+    /// the GC stack. Returns the result if no exception is thrown, the exception if one is, or
+    /// an error if no space is left on the stack.
+    fn include_string<'base, C, N>(
+        &mut self,
+        module: Module<'base>,
+        code: C,
+        filename: N,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        Self: Sized,
+        C: AsRef<str>,
+        N: AsRef<str>,
+    {
+        let global = unsafe { Global::new() };
+        let code = Value::new(self, code.as_ref())?;
+        let filename = Value::new(self, filename.as_ref())?;
+        Module::base(global)
+            .function("include_string")?
+            .call3(self, module.into(), code, filename)
+    }
+
+    /// Returns information about this frame. If `root_types` is `true`, the type of every value
+    /// that is rooted by this frame is included.
+    fn frame_info(&self, root_types: bool) -> FrameInfo;
+
+    /// Returns information about this frame and all frames it has been nested in, starting with
+    /// this frame. If `root_types` is `true`, the type of every rooted value is included.
+    fn frame_chain(&self, root_types: bool) -> Vec<FrameInfo>;
 }
 
+unsafe impl<'s, 'v, 'borrow, K> Keywords<'borrow> for K where
+    K: AsRef<[(Symbol<'s>, Value<'v, 'borrow>)]>
+{
+}
+unsafe impl<'v, 'borrow> Keywords<'borrow> for Value<'v, 'borrow> {}
+unsafe impl<'v, 'borrow> Keywords<'borrow> for NamedTuple<'v, 'borrow> {}
+
 p!(TemporarySymbol, String);
 p!(TemporarySymbol, &dyn AsRef<str>);
 p!(TemporarySymbol, &'a str, 'a);
@@ -453,6 +607,49 @@ unsafe impl IntoJulia for &dyn AsRef<str> {
     }
 }
 
+unsafe impl<T: IntoJulia> IntoJulia for Option<T> {
+    unsafe fn into_julia(&self) -> *mut jl_value_t {
+        match self {
+            Some(value) => value.into_julia(),
+            None => jl_nothing,
+        }
+    }
+}
+
+unsafe impl<T: JuliaType> ElementType for T {
+    unsafe fn element_type() -> *mut jl_value_t {
+        T::julia_type().cast()
+    }
+}
+
+unsafe impl<'a> ElementType for &'a str {
+    unsafe fn element_type() -> *mut jl_value_t {
+        jl_string_type.cast()
+    }
+}
+
+unsafe impl<'a> ElementType for Cow<'a, str> {
+    unsafe fn element_type() -> *mut jl_value_t {
+        jl_string_type.cast()
+    }
+}
+
+unsafe impl ElementType for String {
+    unsafe fn element_type() -> *mut jl_value_t {
+        jl_string_type.cast()
+    }
+}
+
+unsafe impl<T: ElementType> ElementType for Option<T> {
+    unsafe fn element_type() -> *mut jl_value_t {
+        let elty = T::element_type();
+        with_root(elty, || {
+            let mut types = [jl_nothing_type.cast(), elty];
+            jl_type_union(types.as_mut_ptr(), types.len())
+        })
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_julia_type {
@@ -514,8 +711,38 @@ unsafe impl JuliaType for isize {
     }
 }
 
+// Elements of primitive types that are stored inline are read from the array's data rather than
+// boxed.
+macro_rules! impl_inline_cast_element {
+    ($type:ty) => {
+        unsafe fn cast_element(
+            array: Array<'frame, 'data>,
+            idx: usize,
+        ) -> JlrsResult<Self::Output> {
+            if array.contains_inline::<$type>() {
+                Ok(Self::cast_element_unchecked(array, idx))
+            } else if array.is_value_array() {
+                <Self as Cast>::cast(Value::wrap(pointer_element(array, idx)))
+            } else {
+                Err(JlrsError::WrongType)?
+            }
+        }
+
+        unsafe fn cast_element_unchecked(array: Array<'frame, 'data>, idx: usize) -> Self::Output {
+            if array.is_inline_array() {
+                inline_element::<$type>(array, idx)
+            } else {
+                Self::cast_unchecked(Value::wrap(pointer_element(array, idx)))
+            }
+        }
+    };
+}
+
 macro_rules! impl_primitive_cast {
     ($type:ty, $unboxer:ident) => {
+        impl_primitive_cast!($type, $unboxer, impl_inline_cast_element!($type););
+    };
+    ($type:ty, $unboxer:ident, $($cast_element:tt)*) => {
         unsafe impl<'frame, 'data> Cast<'frame, 'data> for $type {
             type Output = Self;
 
@@ -530,6 +757,8 @@ macro_rules! impl_primitive_cast {
             unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
                 $unboxer(value.ptr().cast()) as _
             }
+
+            $($cast_element)*
         }
     };
 }
@@ -544,7 +773,8 @@ impl_primitive_cast!(i32, jl_unbox_int32);
 impl_primitive_cast!(i64, jl_unbox_int64);
 impl_primitive_cast!(f32, jl_unbox_float32);
 impl_primitive_cast!(f64, jl_unbox_float64);
-impl_primitive_cast!(*mut c_void, jl_unbox_voidpointer);
+// Pointers don't implement `ValidLayout`, elements that are stored inline are boxed.
+impl_primitive_cast!(*mut c_void, jl_unbox_voidpointer,);
 
 #[cfg(not(target_pointer_width = "64"))]
 impl_primitive_cast!(usize, jl_unbox_uint32);
@@ -572,6 +802,8 @@ unsafe impl<'frame, 'data> Cast<'frame, 'data> for bool {
     unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
         jl_unbox_int8(value.ptr()) != 0
     }
+
+    impl_inline_cast_element!(bool);
 }
 
 unsafe impl<'frame, 'data> Cast<'frame, 'data> for char {
@@ -630,6 +862,53 @@ unsafe impl<'frame, 'data> Cast<'frame, 'data> for String {
     }
 }
 
+unsafe impl<'frame, 'data, T> Cast<'frame, 'data> for Option<T>
+where
+    T: Cast<'frame, 'data>,
+{
+    type Output = Option<T::Output>;
+
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        if value.is_nothing() || value.is::<Missing>() {
+            return Ok(None);
+        }
+
+        Ok(Some(T::cast(value)?))
+    }
+
+    unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+        if value.is_nothing() || value.is::<Missing>() {
+            return None;
+        }
+
+        Some(T::cast_unchecked(value))
+    }
+
+    fn cast_field(value: Value<'frame, 'data>, idx: usize) -> JlrsResult<Self::Output> {
+        if idx >= value.n_fields() {
+            Err(JlrsError::OutOfBounds(idx, value.n_fields()))?
+        }
+
+        // A field that is stored inline is boxed, the box is only used to check if the field is
+        // `nothing` or `missing`.
+        let field = unsafe { Value::wrap(jl_fieldref(value.ptr(), idx)) };
+        if field.is_nothing() || field.is::<Missing>() {
+            return Ok(None);
+        }
+
+        Ok(Some(T::cast_field(value, idx)?))
+    }
+
+    unsafe fn cast_field_unchecked(value: Value<'frame, 'data>, idx: usize) -> Self::Output {
+        let field = Value::wrap(jl_fieldref(value.ptr(), idx));
+        if field.is_nothing() || field.is::<Missing>() {
+            return None;
+        }
+
+        Some(T::cast_field_unchecked(value, idx))
+    }
+}
+
 impl<'frame, M: Mode> Frame<'frame> for StaticFrame<'frame, M> {
     fn frame<'nested, T, F: FnOnce(&mut StaticFrame<'nested, M>) -> JlrsResult<T>>(
         &'nested mut self,
@@ -675,8 +954,12 @@ impl<'frame, M: Mode> Frame<'frame> for StaticFrame<'frame, M> {
         self.len
     }
 
-    fn print_memory(&self) {
-        self.memory.print_memory()
+    fn frame_info(&self, root_types: bool) -> FrameInfo {
+        self.memory.frame_info(root_types)
+    }
+
+    fn frame_chain(&self, root_types: bool) -> Vec<FrameInfo> {
+        self.memory.frame_chain(root_types)
     }
 }
 
@@ -710,7 +993,7 @@ impl<'frame, M: Mode> Frame<'frame> for DynamicFrame<'frame, M> {
 
     fn output(&mut self) -> JlrsResult<Output<'frame>> {
         unsafe {
-            let out = self.memory.new_output(self.idx)?;
+            let out = self.memory.new_output()?;
             self.len += 1;
             Ok(out)
         }
@@ -720,8 +1003,12 @@ impl<'frame, M: Mode> Frame<'frame> for DynamicFrame<'frame, M> {
         self.len
     }
 
-    fn print_memory(&self) {
-        self.memory.print_memory()
+    fn frame_info(&self, root_types: bool) -> FrameInfo {
+        self.memory.frame_info(root_types)
+    }
+
+    fn frame_chain(&self, root_types: bool) -> Vec<FrameInfo> {
+        self.memory.frame_chain(root_types)
     }
 }
 
@@ -749,7 +1036,13 @@ impl<'frame> Frame<'frame> for NullFrame<'frame> {
         0
     }
 
-    fn print_memory(&self) {}
+    fn frame_info(&self, _: bool) -> FrameInfo {
+        FrameInfo::null()
+    }
+
+    fn frame_chain(&self, _: bool) -> Vec<FrameInfo> {
+        vec![FrameInfo::null()]
+    }
 }
 
 #[cfg(all(feature = "async", target_os = "linux"))]
@@ -783,7 +1076,7 @@ impl<'frame> Frame<'frame> for AsyncFrame<'frame> {
 
     fn output(&mut self) -> JlrsResult<Output<'frame>> {
         unsafe {
-            let out = self.memory.new_output(self.idx)?;
+            let out = self.memory.new_output()?;
             self.len += 1;
             Ok(out)
         }
@@ -793,19 +1086,24 @@ impl<'frame> Frame<'frame> for AsyncFrame<'frame> {
         self.len
     }
 
-    fn print_memory(&self) {
-        self.memory.print_memory()
+    fn frame_info(&self, root_types: bool) -> FrameInfo {
+        self.memory.frame_info(root_types)
+    }
+
+    fn frame_chain(&self, root_types: bool) -> Vec<FrameInfo> {
+        self.memory.frame_chain(root_types)
     }
 }
 
 pub(crate) mod private {
-    use crate::error::AllocError;
+    use crate::error::{AllocError, JlrsResult};
     #[cfg(all(feature = "async", target_os = "linux"))]
     use crate::frame::AsyncFrame;
-    use crate::frame::{DynamicFrame, FrameIdx, NullFrame, Output, StaticFrame};
+    use crate::frame::{DynamicFrame, NullFrame, Output, StaticFrame};
     #[cfg(all(feature = "async", target_os = "linux"))]
     use crate::mode::Async;
     use crate::mode::{Mode, Sync};
+    use crate::value::named_tuple::{self, NamedTuple};
     use crate::value::string::JuliaString;
     use crate::value::symbol::Symbol;
     use crate::value::{Value, Values};
@@ -819,6 +1117,19 @@ pub(crate) mod private {
     // crate.
     pub struct Internal;
 
+    // Only types that are guaranteed to be used from a thread known to Julia can control the GC.
+    pub trait Gc {}
+
+    pub trait Keywords<'borrow> {
+        // Convert the keyword arguments to a `NamedTuple`. The result and all intermediate values
+        // are rooted in `frame`, unless the keyword arguments are already a `NamedTuple`.
+        unsafe fn named_tuple<'fr, F: super::Frame<'fr>>(
+            &self,
+            frame: &mut F,
+            _: Internal,
+        ) -> JlrsResult<*mut jl_value_t>;
+    }
+
     // safety: never return the symbol to the user without assigning the 'base lifetime.
     pub trait TemporarySymbol {
         unsafe fn temporary_symbol<'symbol>(&self, _: Internal) -> Symbol<'symbol>;
@@ -903,6 +1214,45 @@ pub(crate) mod private {
         }
     }
 
+    impl<'s, 'v, 'borrow, K> Keywords<'borrow> for K
+    where
+        K: AsRef<[(Symbol<'s>, Value<'v, 'borrow>)]>,
+    {
+        unsafe fn named_tuple<'fr, F: super::Frame<'fr>>(
+            &self,
+            frame: &mut F,
+            _: Internal,
+        ) -> JlrsResult<*mut jl_value_t> {
+            let (names, values): (Vec<_>, Vec<_>) = self.as_ref().iter().copied().unzip();
+            let output = frame.output()?;
+            Ok(named_tuple::new_output(frame, output, &names, &values)?.ptr())
+        }
+    }
+
+    impl<'v, 'borrow> Keywords<'borrow> for Value<'v, 'borrow> {
+        unsafe fn named_tuple<'fr, F: super::Frame<'fr>>(
+            &self,
+            frame: &mut F,
+            _: Internal,
+        ) -> JlrsResult<*mut jl_value_t> {
+            if self.is::<NamedTuple>() {
+                return Ok(self.ptr());
+            }
+
+            Ok(NamedTuple::from_struct(frame, *self)?.ptr())
+        }
+    }
+
+    impl<'v, 'borrow> Keywords<'borrow> for NamedTuple<'v, 'borrow> {
+        unsafe fn named_tuple<'fr, F: super::Frame<'fr>>(
+            &self,
+            _: &mut F,
+            _: Internal,
+        ) -> JlrsResult<*mut jl_value_t> {
+            Ok(self.ptr())
+        }
+    }
+
     impl<'frame, M: Mode> Frame<'frame> for StaticFrame<'frame, M> {
         type U = M;
         unsafe fn protect(
@@ -971,10 +1321,7 @@ pub(crate) mod private {
             value: *mut jl_value_t,
             _: Internal,
         ) -> Value<'output, 'static> {
-            unsafe {
-                self.memory
-                    .protect(FrameIdx::default(), output.offset, value.cast())
-            }
+            unsafe { self.memory.protect_output(output, value.cast()) }
         }
     }
 
@@ -985,7 +1332,7 @@ pub(crate) mod private {
             value: *mut jl_value_t,
             _: Internal,
         ) -> Result<Value<'frame, 'static>, AllocError> {
-            let out = self.memory.protect(self.idx, value.cast())?;
+            let out = self.memory.protect(value.cast())?;
             self.len += 1;
             Ok(out)
         }
@@ -996,21 +1343,12 @@ pub(crate) mod private {
             _: Internal,
         ) -> Result<Values<'frame>, AllocError> {
             unsafe {
-                let offset = self.len;
-                // TODO: check capacity
-
-                for value in values {
-                    match self.memory.protect(self.idx, value.into_julia().cast()) {
-                        Ok(_) => (),
-                        Err(AllocError::StackOverflow(_, n)) => {
-                            return Err(AllocError::StackOverflow(values.len(), n))
-                        }
-                        _ => unreachable!(),
-                    }
-                    self.len += 1;
-                }
-
-                Ok(self.memory.as_values(self.idx, offset, values.len()))
+                let values = self.memory.protect_many(
+                    values.len(),
+                    values.iter().map(|value| value.into_julia().cast()),
+                )?;
+                self.len += values.len();
+                Ok(values)
             }
         }
 
@@ -1020,15 +1358,12 @@ pub(crate) mod private {
             _: Internal,
         ) -> Result<Values<'frame>, AllocError> {
             unsafe {
-                let offset = self.len;
-                // TODO: check capacity in advance
-
-                for value in values {
-                    self.memory.protect(self.idx, value.into_julia().cast())?;
-                    self.len += 1;
-                }
-
-                Ok(self.memory.as_values(self.idx, offset, values.len()))
+                let values = self.memory.protect_many(
+                    values.len(),
+                    values.iter().map(|value| value.into_julia().cast()),
+                )?;
+                self.len += values.len();
+                Ok(values)
             }
         }
 
@@ -1087,7 +1422,7 @@ pub(crate) mod private {
             value: *mut jl_value_t,
             _: Internal,
         ) -> Result<Value<'frame, 'static>, AllocError> {
-            let out = self.memory.protect(self.idx, value.cast())?;
+            let out = self.memory.protect(value.cast())?;
             self.len += 1;
             Ok(out)
         }
@@ -1098,21 +1433,12 @@ pub(crate) mod private {
             _: Internal,
         ) -> Result<Values<'frame>, AllocError> {
             unsafe {
-                let offset = self.len;
-                // TODO: check capacity
-
-                for value in values {
-                    match self.memory.protect(self.idx, value.into_julia().cast()) {
-                        Ok(_) => (),
-                        Err(AllocError::StackOverflow(_, n)) => {
-                            return Err(AllocError::StackOverflow(values.len(), n))
-                        }
-                        _ => unreachable!(),
-                    }
-                    self.len += 1;
-                }
-
-                Ok(self.memory.as_values(self.idx, offset, values.len()))
+                let values = self.memory.protect_many(
+                    values.len(),
+                    values.iter().map(|value| value.into_julia().cast()),
+                )?;
+                self.len += values.len();
+                Ok(values)
             }
         }
 
@@ -1122,15 +1448,12 @@ pub(crate) mod private {
             _: Internal,
         ) -> Result<Values<'frame>, AllocError> {
             unsafe {
-                let offset = self.len;
-                // TODO: check capacity in advance
-
-                for value in values {
-                    self.memory.protect(self.idx, value.into_julia().cast())?;
-                    self.len += 1;
-                }
-
-                Ok(self.memory.as_values(self.idx, offset, values.len()))
+                let values = self.memory.protect_many(
+                    values.len(),
+                    values.iter().map(|value| value.into_julia().cast()),
+                )?;
+                self.len += values.len();
+                Ok(values)
             }
         }
 
@@ -14,40 +14,54 @@
 //! [`Values`]: struct.Values.html
 
 use self::array::{Array, Dimensions};
+use self::collections::{new_dict, new_set, pointer_element};
 use self::datatype::DataType;
+use self::method::{Method, Methods};
+use self::missing::Missing;
 use self::module::Module;
 use self::symbol::Symbol;
 use crate::error::{JlrsError, JlrsResult};
-use crate::frame::Output;
+use crate::frame::{DynamicFrame, Output};
 use crate::global::Global;
 use crate::impl_julia_type;
+use crate::interrupt::{check_interrupted, release_flag, Interrupt};
+use crate::mode::Sync;
+use crate::stack::{Dynamic, RawStack, StackView};
 use crate::traits::{
-    private::Internal, Cast, Frame, IntoJulia, JuliaType, JuliaTypecheck, TemporarySymbol,
-    ValidLayout,
+    private::Internal, Cast, ElementType, Frame, IntoJulia, JuliaType, JuliaTypecheck, Keywords,
+    TemporarySymbol, ValidLayout,
 };
 use jl_sys::{
     jl_alloc_array_1d, jl_alloc_array_2d, jl_alloc_array_3d, jl_any_type, jl_apply_array_type,
-    jl_apply_tuple_type_v, jl_call, jl_call0, jl_call1, jl_call2, jl_call3, jl_datatype_t,
-    jl_exception_occurred, jl_field_index, jl_field_isptr, jl_field_names, jl_fieldref,
-    jl_fieldref_noalloc, jl_get_nth_field, jl_get_nth_field_noalloc, jl_is_kind, jl_new_array,
-    jl_new_struct_uninit, jl_nfields, jl_ptr_to_array, jl_ptr_to_array_1d, jl_set_nth_field,
-    jl_subtype, jl_svec_data, jl_svec_len, jl_typeof, jl_typeof_str, jl_value_t,
+    jl_apply_tuple_type_v, jl_array_data, jl_call, jl_call0, jl_call1, jl_call2, jl_call3,
+    jl_datatype_t, jl_egal, jl_exception_occurred, jl_field_index, jl_field_isptr, jl_field_names,
+    jl_fieldref, jl_fieldref_noalloc, jl_get_nth_field, jl_get_nth_field_noalloc, jl_is_kind,
+    jl_isa, jl_new_array, jl_new_struct_uninit, jl_nfields, jl_object_id, jl_ptr_to_array,
+    jl_ptr_to_array_1d, jl_set_nth_field, jl_subtype, jl_svec_data, jl_svec_len, jl_true,
+    jl_type_morespecific, jl_typeof, jl_typeof_str, jl_value_t,
 };
 use std::borrow::BorrowMut;
-use std::ffi::CStr;
-use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::ffi::{c_void, CStr};
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::ptr::null_mut;
 use std::slice;
+use std::time::Duration;
 
 pub mod array;
+pub mod code_info;
 pub mod code_instance;
+pub mod collections;
 pub mod datatype;
 pub mod expr;
+pub mod ir;
 pub mod method;
 pub mod method_instance;
 pub mod method_table;
+pub mod missing;
 pub mod module;
+pub mod named_tuple;
 pub mod simple_vector;
 pub mod string;
 pub mod symbol;
@@ -265,6 +279,51 @@ impl<'frame, 'data> Value<'frame, 'data> {
         }
     }
 
+    /// Create a new `Dict` with the given entries, the key and value types of the `Dict` are
+    /// derived from the [`ElementType`] implementations of `K` and `V`. The entries are added by
+    /// calling `setindex!`, if this throws an exception it's returned as the error of the
+    /// `CallResult`. The `Dict` or exception is protected from garbage collection inside the frame
+    /// used to create it, this takes one slot on the GC stack. Each entry is converted and added
+    /// in a nested frame with three slots.
+    ///
+    /// Returns an error if there is not enough space on the stack.
+    ///
+    /// [`ElementType`]: ../traits/trait.ElementType.html
+    pub fn new_dict<'k, 'v, K, V, I, F>(
+        frame: &mut F,
+        entries: I,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        K: IntoJulia + ElementType + 'k,
+        V: IntoJulia + ElementType + 'v,
+        I: IntoIterator<Item = (&'k K, &'v V)>,
+        F: Frame<'frame>,
+    {
+        unsafe { new_dict(frame, entries) }
+    }
+
+    /// Create a new `Set` with the given elements, the element type of the `Set` is derived from
+    /// the [`ElementType`] implementation of `T`. The elements are added by calling `push!`, if
+    /// this throws an exception it's returned as the error of the `CallResult`. The `Set` or
+    /// exception is protected from garbage collection inside the frame used to create it, this
+    /// takes one slot on the GC stack. Each element is converted and added in a nested frame with
+    /// three slots.
+    ///
+    /// Returns an error if there is not enough space on the stack.
+    ///
+    /// [`ElementType`]: ../traits/trait.ElementType.html
+    pub fn new_set<'t, T, I, F>(
+        frame: &mut F,
+        elements: I,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        T: IntoJulia + ElementType + 't,
+        I: IntoIterator<Item = &'t T>,
+        F: Frame<'frame>,
+    {
+        unsafe { new_set(frame, elements) }
+    }
+
     /// Create a new Julia value using the output to protect it from garbage collection, any type
     /// that implements [`IntoJulia`] can be converted using this function. The value will be
     /// protected from garbage collection until the frame the output belongs to goes out of scope.
@@ -289,6 +348,11 @@ impl<'frame, 'data> Value<'frame, 'data> {
         }
     }
 
+    /// Returns true if the value is `missing`.
+    pub fn is_missing(self) -> bool {
+        self.is::<Missing>()
+    }
+
     /// Performs the given type check. For types that represent Julia data, this check comes down
     /// to checking if the data has that type. This works for primitive types, for example:
     ///
@@ -622,6 +686,112 @@ impl<'frame, 'data> Value<'frame, 'data> {
         unsafe { jl_subtype(self.ptr(), sup.ptr()) != 0 }
     }
 
+    /// Returns true if `self` is an instance of the type `ty`, ie `self isa ty`.
+    pub fn isa(self, ty: Value) -> bool {
+        unsafe { jl_isa(self.ptr(), ty.ptr()) != 0 }
+    }
+
+    /// Returns true if `self` and `other` are egal, ie `self === other`. Mutable values are egal
+    /// if they're the same object, immutable values if they have the same type and their contents
+    /// are egal. This is also how `Value` implements `PartialEq`.
+    pub fn egal(self, other: Value) -> bool {
+        unsafe {
+            if self.ptr().is_null() || other.ptr().is_null() {
+                return self.ptr() == other.ptr();
+            }
+
+            jl_egal(self.ptr(), other.ptr()) != 0
+        }
+    }
+
+    /// Returns the object id of this value, ie `objectid(self)`. Values that are egal have the
+    /// same object id, this is also how `Value` implements `Hash`.
+    pub fn object_id(self) -> usize {
+        unsafe {
+            if self.ptr().is_null() {
+                return 0;
+            }
+
+            jl_object_id(self.ptr())
+        }
+    }
+
+    /// Returns true if `self` and `other` are equal according to `Base.isequal`. Unlike
+    /// [`Value::egal`], this calls a Julia function so it can throw. If an exception is thrown
+    /// `JlrsError::Exception` is returned. This takes one slot on the GC stack.
+    ///
+    /// [`Value::egal`]: struct.Value.html#method.egal
+    pub fn isequal<'fr, F>(self, frame: &mut F, other: Value) -> JlrsResult<bool>
+    where
+        F: Frame<'fr>,
+    {
+        let func = unsafe { Module::base(Global::new()).function("isequal")? };
+        func.call2(frame, self, other)?
+            .map_err(|e| JlrsError::Exception(e.type_name().into()))?
+            .cast::<bool>()
+    }
+
+    /// Returns the hash of this value according to `Base.hash`. Values that are `isequal` have
+    /// the same hash. If an exception is thrown `JlrsError::Exception` is returned. This takes
+    /// one slot on the GC stack.
+    pub fn hash<'fr, F>(self, frame: &mut F) -> JlrsResult<u64>
+    where
+        F: Frame<'fr>,
+    {
+        let func = unsafe { Module::base(Global::new()).function("hash")? };
+        func.call1(frame, self)?
+            .map_err(|e| JlrsError::Exception(e.type_name().into()))?
+            .cast::<u64>()
+    }
+
+    /// Renders this value the way it's displayed in the REPL, ie by calling
+    /// `show(io, MIME"text/plain"(), self)`. If an exception is thrown `JlrsError::Exception` is
+    /// returned. No slots on the GC stack are used, a nested frame with eight slots is used to
+    /// render the value.
+    pub fn display_string<'fr, F>(self, frame: &mut F) -> JlrsResult<String>
+    where
+        F: Frame<'fr>,
+    {
+        self.show_string(frame, Some("text/plain"), false)
+    }
+
+    /// Renders this value by calling `repr(self)`. If an exception is thrown
+    /// `JlrsError::Exception` is returned. No slots on the GC stack are used, a nested frame with
+    /// eight slots is used to render the value.
+    pub fn repr_string<'fr, F>(self, frame: &mut F) -> JlrsResult<String>
+    where
+        F: Frame<'fr>,
+    {
+        self.show_string(frame, None, false)
+    }
+
+    /// Renders this value by calling `show(io, self)`, or `show(io, MIME(mime), self)` if a MIME
+    /// type is provided. If `compact` is `true`, `io` is an `IOContext` with `:compact` set to
+    /// `true`. If an exception is thrown `JlrsError::Exception` is returned. No slots on the GC
+    /// stack are used, a nested frame with eight slots is used to render the value.
+    pub fn show_string<'fr, F>(
+        self,
+        frame: &mut F,
+        mime: Option<&str>,
+        compact: bool,
+    ) -> JlrsResult<String>
+    where
+        F: Frame<'fr>,
+    {
+        unsafe { render(frame, self, Render::Show(mime), compact) }
+    }
+
+    /// Renders this exception by calling `showerror(io, self)`, this shows the same message
+    /// Julia prints when the exception is not caught. If an exception is thrown
+    /// `JlrsError::Exception` is returned. No slots on the GC stack are used, a nested frame with
+    /// eight slots is used to render the exception.
+    pub fn error_string<'fr, F>(self, frame: &mut F) -> JlrsResult<String>
+    where
+        F: Frame<'fr>,
+    {
+        unsafe { render(frame, self, Render::Error, false) }
+    }
+
     /// Returns true if `self` is the type of a `DataType`, `UnionAll`, `Union`, or `Union{}` (the
     /// bottom type).
     pub fn is_kind(self) -> bool {
@@ -638,6 +808,229 @@ impl<'frame, 'data> Value<'frame, 'data> {
         }
     }
 
+    /// Returns an iterator over the methods of this function by calling `Base.methods`. This
+    /// takes one slot on the GC stack. If an exception is thrown `JlrsError::Exception` is
+    /// returned.
+    pub fn methods<'fr, F>(self, frame: &mut F) -> JlrsResult<Methods<'fr>>
+    where
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let global = Global::new();
+            let list = Module::base(global)
+                .function("methods")?
+                .call1(frame, self)?
+                .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+            let methods = list.get_field_noalloc("ms")?.cast::<Array>()?;
+            Ok(Methods::new(methods.ptr()))
+        }
+    }
+
+    /// Returns the method that is called when this function is called with arguments of the
+    /// given types, or `None` if no method matches. If several methods match and none of them is
+    /// more specific than all others, the call is ambiguous and `JlrsError::AmbiguousMethod` is
+    /// returned. The method is rooted by the function's method table, a nested frame with three
+    /// slots is used to find it. If an exception is thrown `JlrsError::Exception` is returned.
+    pub fn which<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        types: V,
+    ) -> JlrsResult<Option<Method<'fr>>>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            // `Base.which` doesn't detect ambiguities, so the matching methods are compared
+            // directly.
+            let method = frame.frame(3, |frame| {
+                let global = Global::new();
+                let types = signature_type(&mut *frame, types.as_ref())?;
+                let signature = Module::base(global)
+                    .function("signature_type")?
+                    .call2(&mut *frame, self, types)?
+                    .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+                let matching: Vec<Method> = self
+                    .methods(&mut *frame)?
+                    .filter(|m| jl_subtype(signature.ptr(), m.signature().ptr()) != 0)
+                    .collect();
+
+                if matching.is_empty() {
+                    return Ok(null_mut());
+                }
+
+                let most_specific = matching.iter().find(|m| {
+                    matching.iter().all(|other| {
+                        m.ptr() == other.ptr()
+                            || jl_type_morespecific(m.signature().ptr(), other.signature().ptr())
+                                != 0
+                    })
+                });
+
+                match most_specific {
+                    Some(method) => Ok(method.ptr()),
+                    None => Err(JlrsError::AmbiguousMethod)?,
+                }
+            })?;
+
+            if method.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(Method::wrap(method)))
+            }
+        }
+    }
+
+    /// Returns the inferred return types of the methods of this function that match the given
+    /// argument types by calling `Base.return_types`, one for every matching method. The types
+    /// are rooted by an array that takes one slot on the GC stack, a nested frame with two slots
+    /// is used to infer them. If an exception is thrown `JlrsError::Exception` is returned.
+    pub fn return_types<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        types: V,
+    ) -> JlrsResult<Vec<Value<'fr, 'static>>>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let return_types = frame.frame(2, |frame| {
+                let signature = signature_type(&mut *frame, types.as_ref())?;
+                let return_types = Module::base(Global::new())
+                    .function("return_types")?
+                    .call2(&mut *frame, self, signature)?
+                    .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+                Ok(return_types.cast::<Array>()?.ptr())
+            })?;
+
+            frame
+                .protect(return_types.cast(), Internal)
+                .map_err(Into::<Box<_>>::into)?;
+
+            let data: *mut *mut jl_value_t = jl_array_data(return_types.cast()).cast();
+            let len = Dimensions::from_array(return_types).size();
+            Ok((0..len).map(|i| Value::wrap(*data.add(i))).collect())
+        }
+    }
+
+    /// Returns the return type that is inferred when this function is called with arguments of
+    /// the given types by calling `Core.Compiler.return_type`. If multiple methods match, this
+    /// is the union of their return types. A kernel is type-stable for these argument types if
+    /// the result is a concrete type. This takes one slot on the GC stack, a nested frame with
+    /// one slot is used to infer it. If an exception is thrown `JlrsError::Exception` is
+    /// returned.
+    pub fn return_type<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        types: V,
+    ) -> JlrsResult<Value<'fr, 'static>>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let return_type = frame.frame(1, |frame| {
+                let signature = signature_type(&mut *frame, types.as_ref())?;
+                let res = jl_call2(
+                    Module::core(Global::new())
+                        .submodule("Compiler")?
+                        .function("return_type")?
+                        .ptr(),
+                    self.ptr(),
+                    signature.ptr(),
+                );
+                Ok(unrooted_result(res)?.ptr())
+            })?;
+
+            frame
+                .protect(return_type, Internal)
+                .map_err(Into::<Box<_>>::into)
+        }
+    }
+
+    /// Returns the lowered code of the methods of this function that match the given argument
+    /// types, as shown by `code_lowered`. No slots on the GC stack are used, a nested frame with
+    /// two slots is used to generate it and one with eight slots to render it. If an exception is
+    /// thrown `JlrsError::Exception` is returned.
+    pub fn code_lowered<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        types: V,
+    ) -> JlrsResult<String>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let func = Module::base(Global::new()).function("code_lowered")?;
+            code_string(frame, func, self, types.as_ref(), false)
+        }
+    }
+
+    /// Returns the type-inferred code of the methods of this function that match the given
+    /// argument types, as shown by `code_typed`. No slots on the GC stack are used, a nested
+    /// frame with two slots is used to generate it and one with eight slots to render it. If an
+    /// exception is thrown `JlrsError::Exception` is returned.
+    pub fn code_typed<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        types: V,
+    ) -> JlrsResult<String>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let func = Module::base(Global::new()).function("code_typed")?;
+            code_string(frame, func, self, types.as_ref(), false)
+        }
+    }
+
+    /// Returns the LLVM IR that is generated for the method of this function that matches the
+    /// given argument types, as printed by `InteractiveUtils.code_llvm`. The `InteractiveUtils`
+    /// package is loaded if necessary. No slots on the GC stack are used, a nested frame with
+    /// two slots is used to generate it. If an exception is thrown `JlrsError::Exception` is
+    /// returned.
+    pub fn code_llvm<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        types: V,
+    ) -> JlrsResult<String>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let func = Module::require(Global::new(), "InteractiveUtils")?.function("code_llvm")?;
+            code_string(frame, func, self, types.as_ref(), true)
+        }
+    }
+
+    /// Returns the native assembly that is generated for the method of this function that
+    /// matches the given argument types, as printed by `InteractiveUtils.code_native`. The
+    /// `InteractiveUtils` package is loaded if necessary. No slots on the GC stack are used, a
+    /// nested frame with two slots is used to generate it. If an exception is thrown
+    /// `JlrsError::Exception` is returned.
+    pub fn code_native<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        types: V,
+    ) -> JlrsResult<String>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let func =
+                Module::require(Global::new(), "InteractiveUtils")?.function("code_native")?;
+            code_string(frame, func, self, types.as_ref(), true)
+        }
+    }
+
     /// Allocates a new n-dimensional array in Julia.
     ///
     /// Creating an an array with 1, 2 or 3 dimensions requires one slot on the GC stack. If you
@@ -876,6 +1269,44 @@ impl<'frame, 'data> Value<'frame, 'data> {
         }
     }
 
+    /// Call this value as a function that takes several positional arguments and keyword
+    /// arguments, this takes one slot on the GC stack. The keyword arguments are converted to a
+    /// `NamedTuple` in a nested frame and the function is called through the keyword sorter
+    /// returned by `Core.kwfunc`. Returns the result of this function call if no exception is
+    /// thrown, the exception if one is, or an error if the keyword arguments can't be converted
+    /// or no space is left on the stack. See [`Keywords`] for the types that can be used as
+    /// keyword arguments.
+    ///
+    /// [`Keywords`]: ../traits/trait.Keywords.html
+    pub fn call_keywords<'value, 'borrow, V, K, F>(
+        self,
+        frame: &mut F,
+        mut args: V,
+        keywords: K,
+    ) -> JlrsResult<CallResult<'frame, 'borrow>>
+    where
+        V: AsMut<[Value<'value, 'borrow>]>,
+        K: Keywords<'borrow>,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let res = frame.dynamic_frame(|frame| {
+                let kwargs = keywords.named_tuple(frame, Internal)?;
+                let kwsorter = kwsorter(frame, self)?;
+
+                let args = args.as_mut();
+                let mut vals = Vec::with_capacity(2 + args.len());
+                vals.push(kwargs);
+                vals.push(self.ptr());
+                vals.extend(args.iter().map(|arg| arg.ptr()));
+
+                Ok(jl_call(kwsorter.ptr(), vals.as_mut_ptr(), vals.len() as _))
+            })?;
+
+            try_protect(frame, res)
+        }
+    }
+
     /// Call this value as a function that takes several arguments and execute it on another
     /// thread in Julia created with `Base.@spawn`, this takes two slots on the GC stack. Returns
     /// the result of this function call if no exception is thrown, the exception if one is, or an
@@ -895,6 +1326,154 @@ impl<'frame, 'data> Value<'frame, 'data> {
         unsafe { Ok(crate::julia_future::JuliaFuture::new(frame, self, args)?.await) }
     }
 
+    /// Call this value as a function that takes several positional arguments and keyword
+    /// arguments and execute it on another thread in Julia created with `Base.@spawn`. The
+    /// keyword arguments are converted to a `NamedTuple` and the function is called through the
+    /// keyword sorter returned by `Core.kwfunc`. This takes at least four slots on the GC stack,
+    /// more if the keyword arguments have to be converted. Returns the result of this function
+    /// call if no exception is thrown, the exception if one is, or an error if the keyword
+    /// arguments can't be converted or no space is left on the stack. See [`Keywords`] for the
+    /// types that can be used as keyword arguments.
+    ///
+    /// This function can only be called with an `AsyncFrame`, while you're waiting for this
+    /// function to complete, other tasks are able to progress.
+    ///
+    /// [`Keywords`]: ../traits/trait.Keywords.html
+    #[cfg(all(feature = "async", target_os = "linux"))]
+    pub async fn call_async_keywords<'value, 'borrow, V, K>(
+        self,
+        frame: &mut crate::frame::AsyncFrame<'frame>,
+        mut args: V,
+        keywords: K,
+    ) -> JlrsResult<CallResult<'frame, 'borrow>>
+    where
+        V: AsMut<[Value<'value, 'borrow>]>,
+        K: Keywords<'borrow>,
+    {
+        unsafe {
+            let kwargs = keywords.named_tuple(frame, Internal)?;
+            let kwsorter = kwsorter(frame, self)?;
+
+            let args = args.as_mut();
+            let mut vals: Vec<Value<'_, 'borrow>> = Vec::with_capacity(2 + args.len());
+            vals.push(Value::wrap(kwargs));
+            vals.push(Value::wrap(self.ptr()));
+            vals.extend_from_slice(args);
+
+            Ok(crate::julia_future::JuliaFuture::new(frame, kwsorter, vals)?.await)
+        }
+    }
+
+    /// Call this value as a function that takes several arguments in a new Julia task, which is
+    /// interrupted if it hasn't completed after `timeout`. This takes four slots on the GC
+    /// stack. Returns the result of this function call if no exception is thrown, the exception
+    /// if one is, or an error if the call is interrupted or no space is left on the stack. A
+    /// function that never yields can't be interrupted, see the [`interrupt`] module for more
+    /// information. You must include `jlrs.jl` to use this function.
+    ///
+    /// [`interrupt`]: ../interrupt/index.html
+    pub fn call_timeout<'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        args: V,
+        timeout: Duration,
+    ) -> JlrsResult<CallResult<'frame, 'borrow>>
+    where
+        V: AsMut<[Value<'value, 'borrow>]>,
+        F: Frame<'frame>,
+    {
+        self.call_interruptible(frame, args, &Interrupt::new(), Some(timeout))
+    }
+
+    /// Call this value as a function that takes several arguments in a new Julia task, which is
+    /// interrupted when [`Interrupt::interrupt`] is called or, if a timeout is given, it hasn't
+    /// completed after `timeout`. This takes four slots on the GC stack. Returns the result of
+    /// this function call if no exception is thrown, the exception if one is, or an error if the
+    /// call is interrupted or no space is left on the stack. A function that never yields can't
+    /// be interrupted, see the [`interrupt`] module for more information. You must include
+    /// `jlrs.jl` to use this function.
+    ///
+    /// [`Interrupt::interrupt`]: ../interrupt/struct.Interrupt.html#method.interrupt
+    /// [`interrupt`]: ../interrupt/index.html
+    pub fn call_interruptible<'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        mut args: V,
+        interrupt: &Interrupt,
+        timeout: Option<Duration>,
+    ) -> JlrsResult<CallResult<'frame, 'borrow>>
+    where
+        V: AsMut<[Value<'value, 'borrow>]>,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let global = Global::new();
+            let func = Module::main(global)
+                .submodule("Jlrs")?
+                .function("interruptiblecall")?;
+            let release = Value::new(frame, release_flag as *mut c_void)?;
+            let timeout = Value::new(frame, timeout.map_or(-1.0, |t| t.as_secs_f64()))?;
+            let flag = new_flag(frame, interrupt)?;
+
+            let args = args.as_mut();
+            let mut vals = Vec::with_capacity(4 + args.len());
+            vals.push(self.ptr());
+            vals.push(flag.ptr());
+            vals.push(release.ptr());
+            vals.push(timeout.ptr());
+            vals.extend(args.iter().map(|arg| arg.ptr()));
+
+            let res = jl_call(func.ptr(), vals.as_mut_ptr(), vals.len() as _);
+            check_interrupted(try_protect(frame, res)?)
+        }
+    }
+
+    /// Call this value as a function that takes several arguments in a new Julia task on
+    /// another thread, which is interrupted when [`Interrupt::interrupt`] is called or, if a
+    /// timeout is given, it hasn't completed after `timeout`. This takes five slots on the GC
+    /// stack. Returns the result of this function call if no exception is thrown, the exception
+    /// if one is, or an error if the call is interrupted or no space is left on the stack. A
+    /// function that never yields can't be interrupted, see the [`interrupt`] module for more
+    /// information. You must include `jlrs.jl` to use this function.
+    ///
+    /// This function can only be called with an `AsyncFrame`, while you're waiting for this
+    /// function to complete, other tasks are able to progress.
+    ///
+    /// [`Interrupt::interrupt`]: ../interrupt/struct.Interrupt.html#method.interrupt
+    /// [`interrupt`]: ../interrupt/index.html
+    #[cfg(all(feature = "async", target_os = "linux"))]
+    pub async fn call_async_interruptible<'value, 'borrow, V>(
+        self,
+        frame: &mut crate::frame::AsyncFrame<'frame>,
+        mut args: V,
+        interrupt: &Interrupt,
+        timeout: Option<Duration>,
+    ) -> JlrsResult<CallResult<'frame, 'borrow>>
+    where
+        V: AsMut<[Value<'value, 'borrow>]>,
+    {
+        unsafe {
+            let global = Global::new();
+            let func = Module::main(global)
+                .submodule("Jlrs")?
+                .function("interruptiblecall")?;
+            let release = Value::new(frame, release_flag as *mut c_void)?;
+            let timeout = Value::new(frame, timeout.map_or(-1.0, |t| t.as_secs_f64()))?;
+            let flag = new_flag(frame, interrupt)?;
+
+            let args = args.as_mut();
+            let mut vals: Vec<Value<'_, 'borrow>> = Vec::with_capacity(4 + args.len());
+            vals.push(Value::wrap(self.ptr()));
+            vals.push(flag);
+            vals.push(release);
+            vals.push(timeout);
+            vals.extend_from_slice(args);
+
+            let res = crate::julia_future::JuliaFuture::new(frame, func, vals)?.await;
+            check_interrupted(res)
+        }
+    }
+
     /// Call this value as a function that takes several arguments in a single `Values`, this
     /// takes one slot on the GC stack. Returns the result of this function call if no exception
     /// is thrown, the exception if one is, or an error if no space is left on the stack.
@@ -912,6 +1491,34 @@ impl<'frame, 'data> Value<'frame, 'data> {
         }
     }
 
+    /// Apply this value, which must be a parametric type like `Vector` or `Dict`, to the type
+    /// parameters `params` by calling `Core.apply_type`, eg `Dict` and `[String, Int64]` return
+    /// `Dict{String,Int64}`. This takes one slot on the GC stack. Returns the new type if the
+    /// parameters are valid, the exception if they're not, or an error if no space is left on
+    /// the stack.
+    pub fn apply_type<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        mut params: V,
+    ) -> JlrsResult<CallResult<'fr, 'static>>
+    where
+        V: AsMut<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let global = Global::new();
+            let func = Module::core(global).function("apply_type")?;
+
+            let params = params.as_mut();
+            let mut vals = Vec::with_capacity(1 + params.len());
+            vals.push(self.ptr());
+            vals.extend(params.iter().map(|param| param.ptr()));
+
+            let res = jl_call(func.ptr(), vals.as_mut_ptr(), vals.len() as _);
+            try_protect(frame, res)
+        }
+    }
+
     /// Returns an anonymous function that wraps this value in a try-catch block. Calling this
     /// anonymous function with some arguments will call the value as a function with those
     /// arguments and return its result, or catch the exception, print the stackstrace, and
@@ -958,8 +1565,92 @@ impl<'frame, 'data> Debug for Value<'frame, 'data> {
     }
 }
 
+impl<'frame, 'data> PartialEq for Value<'frame, 'data> {
+    fn eq(&self, other: &Self) -> bool {
+        self.egal(*other)
+    }
+}
+
+impl<'frame, 'data> Eq for Value<'frame, 'data> {}
+
+impl<'frame, 'data> Hash for Value<'frame, 'data> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.object_id().hash(state)
+    }
+}
+
+/// Values are displayed by calling `print`, or `show` with the `text/plain` MIME type if the
+/// alternate flag is used. Exceptions are displayed by calling `showerror`. If an exception is
+/// thrown while the value is rendered, only the type names are written. Because no frame is
+/// available, the intermediate values are rooted in a frame on a temporary stack.
+impl<'frame, 'data> Display for Value<'frame, 'data> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let exception = unsafe {
+            if self.ptr().is_null() {
+                return f.write_str("null");
+            }
+
+            Module::core(Global::new()).global("Exception")
+        };
+        let mode = if exception.is_ok_and(|exception| self.isa(exception)) {
+            Render::Error
+        } else if f.alternate() {
+            Render::Show(Some("text/plain"))
+        } else {
+            Render::Print
+        };
+
+        let rendered = unsafe {
+            let mut stack = RawStack::new(RENDER_SLOTS + 4);
+            let mut view = StackView::<Sync, Dynamic>::new(&mut stack);
+            view.new_frame().and_then(|idx| {
+                let mut frame = DynamicFrame::new(idx, view);
+                render(&mut frame, *self, mode, false)
+            })
+        };
+
+        match rendered {
+            Ok(s) => f.write_str(&s),
+            Err(e) => write!(f, "<{} could not be rendered: {}>", self.type_name(), e),
+        }
+    }
+}
+
 impl_julia_type!(Value<'frame, 'data>, jl_any_type, 'frame, 'data);
 
+unsafe impl<'frame, 'data> Cast<'frame, 'data> for Value<'frame, 'data> {
+    type Output = Self;
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        Ok(value)
+    }
+
+    unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+        value
+    }
+
+    // A field that is stored inline would have to be boxed, the box is not rooted.
+    fn cast_field(value: Value<'frame, 'data>, idx: usize) -> JlrsResult<Self::Output> {
+        unsafe { value.get_nth_field_noalloc(idx) }
+    }
+
+    unsafe fn cast_field_unchecked(value: Value<'frame, 'data>, idx: usize) -> Self::Output {
+        Value::wrap(jl_fieldref_noalloc(value.ptr(), idx))
+    }
+
+    // An element that is stored inline would have to be boxed, the box is not rooted.
+    unsafe fn cast_element(array: Array<'frame, 'data>, idx: usize) -> JlrsResult<Self::Output> {
+        if array.is_inline_array() {
+            Err(JlrsError::Inline)?
+        }
+
+        Ok(Value::wrap(pointer_element(array, idx)))
+    }
+
+    unsafe fn cast_element_unchecked(array: Array<'frame, 'data>, idx: usize) -> Self::Output {
+        Value::wrap(pointer_element(array, idx))
+    }
+}
+
 unsafe impl<'frame, 'data> ValidLayout for Value<'frame, 'data> {
     unsafe fn valid_layout(v: Value) -> bool {
         if let Ok(dt) = v.cast::<DataType>() {
@@ -1080,6 +1771,46 @@ impl<'output, 'frame, 'data> WithOutput<'output, Value<'frame, 'data>> {
         }
     }
 
+    /// Call the value as a function that takes several positional arguments and keyword
+    /// arguments and use the `Output` to extend the result's lifetime. The keyword arguments are
+    /// converted to a `NamedTuple` in a nested frame and the function is called through the
+    /// keyword sorter returned by `Core.kwfunc`. This takes no space on the GC stack. Returns
+    /// the result of this function call if no exception is thrown, the exception if one is, or
+    /// an error if the keyword arguments can't be converted or the nested frame can't be
+    /// created. See [`Keywords`] for the types that can be used as keyword arguments.
+    ///
+    /// [`Keywords`]: ../traits/trait.Keywords.html
+    pub fn call_keywords<'value, 'borrow, 'fr, V, K, F>(
+        self,
+        frame: &mut F,
+        mut args: V,
+        keywords: K,
+    ) -> JlrsResult<CallResult<'output, 'borrow>>
+    where
+        'borrow: 'output,
+        V: AsMut<[Value<'value, 'borrow>]>,
+        K: Keywords<'borrow>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            let func = self.value;
+            let res = frame.dynamic_frame(|frame| {
+                let kwargs = keywords.named_tuple(frame, Internal)?;
+                let kwsorter = kwsorter(frame, func)?;
+
+                let args = args.as_mut();
+                let mut vals = Vec::with_capacity(2 + args.len());
+                vals.push(kwargs);
+                vals.push(func.ptr());
+                vals.extend(args.iter().map(|arg| arg.ptr()));
+
+                Ok(jl_call(kwsorter.ptr(), vals.as_mut_ptr(), vals.len() as _))
+            })?;
+
+            Ok(assign(frame, self.output, res))
+        }
+    }
+
     /// Call the value as a function that takes several arguments in a single `Values` and use
     /// the `Output` to extend the result's lifetime. This takes no space on the GC stack. Returns
     /// the result of this function call if no exception is thrown or the exception if one is.
@@ -1256,6 +1987,175 @@ where
     }
 }
 
+// Returns the keyword sorter of `func` by calling `Core.kwfunc`, this takes one slot in `frame`.
+unsafe fn kwsorter<'fr, F>(frame: &mut F, func: Value) -> JlrsResult<Value<'fr, 'static>>
+where
+    F: Frame<'fr>,
+{
+    let global = Global::new();
+    let kwsorter = Module::core(global)
+        .function("kwfunc")?
+        .call1(frame, func)?
+        .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+    Ok(Value::wrap(kwsorter.ptr()))
+}
+
+// Returns a new reference to the flag of `interrupt` as a `Ptr{UInt8}`, this takes one slot in
+// `frame`. The reference is released if the pointer can't be allocated, otherwise it's owned by
+// `Jlrs.interruptiblecall`.
+unsafe fn new_flag<'fr, F>(frame: &mut F, interrupt: &Interrupt) -> JlrsResult<Value<'fr, 'static>>
+where
+    F: Frame<'fr>,
+{
+    let flag = interrupt.flag_ptr();
+    match Value::new(frame, flag) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            release_flag(flag.cast());
+            Err(e)
+        }
+    }
+}
+
+// Returns `Tuple{types...}`, this takes one slot in `frame`.
+unsafe fn signature_type<'fr, F>(frame: &mut F, types: &[Value]) -> JlrsResult<Value<'fr, 'static>>
+where
+    F: Frame<'fr>,
+{
+    let core = Module::core(Global::new());
+    let mut params = Vec::with_capacity(types.len() + 1);
+    params.push(core.global("Tuple")?);
+    params.extend_from_slice(types);
+
+    let signature = core
+        .function("apply_type")?
+        .call(frame, params)?
+        .map_err(|e| JlrsError::Exception(e.type_name().into()))?;
+
+    Ok(Value::wrap(signature.ptr()))
+}
+
+// Calls `reflect(func, Tuple{types...})` in a nested frame with two slots. If `prints` is true,
+// `reflect` prints its output to the `IO` that's passed as its first argument; otherwise its
+// result is rendered with the `text/plain` MIME type in another nested frame.
+unsafe fn code_string<'fr, F>(
+    frame: &mut F,
+    reflect: Value,
+    func: Value,
+    types: &[Value],
+    prints: bool,
+) -> JlrsResult<String>
+where
+    F: Frame<'fr>,
+{
+    let exception = |e: Value| JlrsError::Exception(e.type_name().into());
+    frame.frame(2, |frame| {
+        let signature = signature_type(&mut *frame, types)?;
+        if prints {
+            Module::base(Global::new())
+                .function("sprint")?
+                .call3(&mut *frame, reflect, func, signature)?
+                .map_err(exception)?
+                .cast::<String>()
+        } else {
+            let code = reflect
+                .call2(&mut *frame, func, signature)?
+                .map_err(exception)?;
+            render(&mut *frame, code, Render::Show(Some("text/plain")), false)
+        }
+    })
+}
+
+#[derive(Copy, Clone)]
+enum Render<'a> {
+    Print,
+    Show(Option<&'a str>),
+    Error,
+}
+
+// The number of slots of the nested frame `render` creates.
+const RENDER_SLOTS: usize = 8;
+
+// Writes `value` to an `IOBuffer` and returns its contents. The intermediate values are rooted
+// in a nested frame with `RENDER_SLOTS` slots.
+unsafe fn render<'fr, F>(
+    frame: &mut F,
+    value: Value,
+    mode: Render,
+    compact: bool,
+) -> JlrsResult<String>
+where
+    F: Frame<'fr>,
+{
+    let global = Global::new();
+    let base = Module::base(global);
+    let core = Module::core(global);
+    let exception = |e: Value| JlrsError::Exception(e.type_name().into());
+
+    frame.frame(RENDER_SLOTS, |frame| {
+        let buf = base
+            .function("IOBuffer")?
+            .call0(&mut *frame)?
+            .map_err(exception)?;
+
+        let io = if compact {
+            let pair = base
+                .function("Pair")?
+                .call2(
+                    &mut *frame,
+                    Symbol::new(global, "compact").into(),
+                    Value::wrap(jl_true),
+                )?
+                .map_err(exception)?;
+            base.function("IOContext")?
+                .call2(&mut *frame, buf, pair)?
+                .map_err(exception)?
+        } else {
+            buf
+        };
+
+        match mode {
+            Render::Print => base.function("print")?.call2(&mut *frame, io, value)?,
+            Render::Error => base.function("showerror")?.call2(&mut *frame, io, value)?,
+            Render::Show(None) => base.function("show")?.call2(&mut *frame, io, value)?,
+            Render::Show(Some(mime)) => {
+                let mime_type = core
+                    .function("apply_type")?
+                    .call2(
+                        &mut *frame,
+                        base.global("MIME")?,
+                        Symbol::new(global, mime).into(),
+                    )?
+                    .map_err(exception)?;
+                let mime = mime_type.call0(&mut *frame)?.map_err(exception)?;
+                base.function("show")?.call3(&mut *frame, io, mime, value)?
+            }
+        }
+        .map_err(exception)?;
+
+        let data = base
+            .function("take!")?
+            .call1(&mut *frame, buf)?
+            .map_err(exception)?;
+        base.global("String")?
+            .call1(&mut *frame, data)?
+            .map_err(exception)?
+            .cast::<String>()
+    })
+}
+
+// Converts the result of a call that doesn't need to be rooted because it's immediately
+// unboxed, or returns an error if an exception was thrown.
+unsafe fn unrooted_result<'frame>(res: *mut jl_value_t) -> JlrsResult<Value<'frame, 'static>> {
+    let exc = jl_exception_occurred();
+    if !exc.is_null() {
+        Err(JlrsError::Exception(Value::wrap(exc).type_name().into()))?;
+    }
+
+    Ok(Value::wrap(res))
+}
+
 unsafe fn try_protect<'frame, F>(
     frame: &mut F,
     res: *mut jl_value_t,
@@ -0,0 +1,187 @@
+//! Support for values with the `Core.CodeInfo` type.
+//!
+//! A `CodeInfo` contains the lowered or typed code of a method, it can be obtained by calling
+//! `code_lowered` or `code_typed` from Julia or from the source of a `Method`. Its statements are
+//! expressions or one of the nodes in the [`ir`] module.
+//!
+//! [`ir`]: ../ir/index.html
+
+use super::array::{Array, Dimensions};
+use super::ir::{bits_field, defined_field, pointer_field};
+use super::method_instance::MethodInstance;
+use super::symbol::Symbol;
+use super::Value;
+use crate::error::{JlrsError, JlrsResult};
+use crate::traits::Cast;
+use crate::{impl_julia_type, impl_julia_typecheck, impl_valid_layout};
+use jl_sys::{jl_array_data, jl_code_info_type, jl_method_instance_type, jl_typeis, jl_value_t};
+use std::marker::PhantomData;
+
+/// The code of a method, either lowered or with inferred types.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct CodeInfo<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> CodeInfo<'frame> {
+    pub(crate) unsafe fn wrap(code_info: *mut jl_value_t) -> Self {
+        CodeInfo(code_info, PhantomData)
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn ptr(self) -> *mut jl_value_t {
+        self.0
+    }
+
+    /// The statements, a `Vector{Any}`. `None` if it's undefined.
+    pub fn code(self) -> Option<Array<'frame, 'static>> {
+        unsafe { self.array_field("code") }
+    }
+
+    /// The index into the line table of each statement, a `Vector{Int32}`. An index of 0 means
+    /// no line information is available. `None` if it's undefined.
+    pub fn code_locations(self) -> Option<Array<'frame, 'static>> {
+        unsafe { self.array_field("codelocs") }
+    }
+
+    /// Either the number of SSA values or a `Vector{Any}` with their types after inference.
+    /// `None` if it's undefined.
+    pub fn ssa_value_types(self) -> Option<Value<'frame, 'static>> {
+        unsafe { pointer_field(self.ptr(), "ssavaluetypes") }
+    }
+
+    /// Flags for every statement, a `Vector{UInt8}`. `None` if it's undefined.
+    pub fn ssa_flags(self) -> Option<Array<'frame, 'static>> {
+        unsafe { self.array_field("ssaflags") }
+    }
+
+    /// The `LineInfoNode`s that are referenced by the code locations. `None` if it's undefined.
+    pub fn line_table(self) -> Option<Value<'frame, 'static>> {
+        unsafe { pointer_field(self.ptr(), "linetable") }
+    }
+
+    /// The names of the slots, a `Vector{Symbol}`. The first slot is the function itself, it's
+    /// followed by the arguments and the local variables. `None` if it's undefined.
+    pub fn slot_names(self) -> Option<Array<'frame, 'static>> {
+        unsafe { self.array_field("slotnames") }
+    }
+
+    /// Flags for every slot, a `Vector{UInt8}`. `None` if it's undefined.
+    pub fn slot_flags(self) -> Option<Array<'frame, 'static>> {
+        unsafe { self.array_field("slotflags") }
+    }
+
+    /// The types of the slots after inference, or `nothing`.
+    pub fn slot_types(self) -> Value<'frame, 'static> {
+        unsafe { defined_field(self.ptr(), "slottypes") }
+    }
+
+    /// The inferred return type, or `Any` if this code hasn't been inferred.
+    pub fn return_type(self) -> Value<'frame, 'static> {
+        unsafe { defined_field(self.ptr(), "rettype") }
+    }
+
+    /// The method instance this code has been generated for, `None` if it's unknown.
+    pub fn parent(self) -> Option<MethodInstance<'frame>> {
+        unsafe {
+            let parent = defined_field(self.ptr(), "parent");
+            if jl_typeis(parent.ptr(), jl_method_instance_type) {
+                Some(MethodInstance::wrap(parent.ptr().cast()))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Forward edges to the method instances that must be invalidated.
+    pub fn edges(self) -> Value<'frame, 'static> {
+        unsafe { defined_field(self.ptr(), "edges") }
+    }
+
+    /// The first world age this code is valid in.
+    pub fn min_world(self) -> usize {
+        unsafe { bits_field(self.ptr(), "min_world") }
+    }
+
+    /// The last world age this code is valid in.
+    pub fn max_world(self) -> usize {
+        unsafe { bits_field(self.ptr(), "max_world") }
+    }
+
+    /// Whether this code has been inferred.
+    pub fn inferred(self) -> bool {
+        unsafe { bits_field::<u8>(self.ptr(), "inferred") != 0 }
+    }
+
+    /// Whether this code can be inlined.
+    pub fn inlineable(self) -> bool {
+        unsafe { bits_field::<u8>(self.ptr(), "inlineable") != 0 }
+    }
+
+    /// Whether this code propagates the inbounds context of its caller, i.e. if its method has
+    /// been annotated with `@propagate_inbounds`.
+    pub fn propagate_inbounds(self) -> bool {
+        unsafe { bits_field::<u8>(self.ptr(), "propagate_inbounds") != 0 }
+    }
+
+    /// Whether this code has been annotated with `@pure`, which lets inference assume that it
+    /// has no side effects and only depends on its arguments.
+    pub fn pure(self) -> bool {
+        unsafe { bits_field::<u8>(self.ptr(), "pure") != 0 }
+    }
+
+    /// Returns the file and line the statement at `idx` originates from, the first statement
+    /// has index 0. Returns `None` if `idx` is out of bounds or no line information is available
+    /// for this statement.
+    pub fn line_info(self, idx: usize) -> Option<(Symbol<'frame>, isize)> {
+        unsafe {
+            let locations = self.code_locations()?;
+            if idx >= Dimensions::from_array(locations.ptr()).size() {
+                return None;
+            }
+
+            let location = *jl_array_data(locations.ptr().cast()).cast::<i32>().add(idx);
+            if location <= 0 {
+                return None;
+            }
+
+            let line_table = self.line_table()?.cast::<Array>().ok()?;
+            if location as usize > Dimensions::from_array(line_table.ptr()).size() {
+                return None;
+            }
+
+            let data: *mut *mut jl_value_t = jl_array_data(line_table.ptr().cast()).cast();
+            let node = *data.add(location as usize - 1);
+            let file = pointer_field(node, "file")?.cast::<Symbol>().ok()?;
+            Some((file, bits_field(node, "line")))
+        }
+    }
+
+    unsafe fn array_field(self, name: &str) -> Option<Array<'frame, 'static>> {
+        pointer_field(self.ptr(), name).map(|array| Array::wrap(array.ptr().cast()))
+    }
+}
+
+impl<'frame> Into<Value<'frame, 'static>> for CodeInfo<'frame> {
+    fn into(self) -> Value<'frame, 'static> {
+        unsafe { Value::wrap(self.ptr()) }
+    }
+}
+
+unsafe impl<'frame, 'data> Cast<'frame, 'data> for CodeInfo<'frame> {
+    type Output = Self;
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        if value.is::<Self::Output>() {
+            return unsafe { Ok(Self::cast_unchecked(value)) };
+        }
+
+        Err(JlrsError::NotACodeInfo)?
+    }
+
+    unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+        Self::wrap(value.ptr())
+    }
+}
+
+impl_julia_typecheck!(CodeInfo<'frame>, jl_code_info_type, 'frame);
+impl_julia_type!(CodeInfo<'frame>, jl_code_info_type, 'frame);
+impl_valid_layout!(CodeInfo<'frame>, 'frame);
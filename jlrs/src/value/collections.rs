@@ -0,0 +1,399 @@
+//! Conversions between Julia's collections and the collections in Rust's standard library.
+//!
+//! An array can be cast to a `Vec<T>` if all of its elements can be cast to `T`, the elements
+//! are returned in column-major order. This works for arrays of strings and nested arrays too,
+//! a `Vector{Vector{String}}` can be cast to a `Vec<Vec<String>>`. Similarly, a `Dict` can be
+//! cast to a `HashMap<K, V>` and a `Set` to a `HashSet<T>`.
+//!
+//! Elements of primitive types like `f64` that are stored inline are read from the array's data
+//! directly. Other elements that are stored inline are boxed, and the box is rooted while it's
+//! cast. Such a box is never returned: casting an array whose elements are stored inline to a
+//! `Vec<Value>` returns `JlrsError::Inline`.
+//!
+//! In the other direction, a `Vec<T>` or `&[T]` can be converted to a `Vector` with
+//! [`Value::new`]. A map can be converted to a `Dict` with [`Value::new_dict`] and a set to a
+//! `Set` with [`Value::new_set`], these functions take any iterator of references to entries or
+//! elements. The type parameters of the new collection are derived from the [`ElementType`]
+//! impls of its elements, so a `HashMap<String, f64>` becomes a `Dict{String, Float64}` rather
+//! than a `Dict{Any, Any}`.
+//!
+//! Entries are added to a `Dict` or `Set` by calling `setindex!` or `push!`, if one of these
+//! calls throws an exception it's returned as the error of the `CallResult`.
+//!
+//! [`Value::new`]: ../struct.Value.html#method.new
+//! [`Value::new_dict`]: ../struct.Value.html#method.new_dict
+//! [`Value::new_set`]: ../struct.Value.html#method.new_set
+//! [`ElementType`]: ../../traits/trait.ElementType.html
+
+use super::array::{Array, Dimensions};
+use super::module::Module;
+use super::union_all::UnionAll;
+use super::{CallResult, Value};
+use crate::error::{JlrsError, JlrsResult};
+use crate::frame::StaticFrame;
+use crate::global::Global;
+use crate::traits::{private::Internal, Cast, ElementType, Frame, IntoJulia};
+use jl_sys::{
+    jl_alloc_array_1d, jl_apply_array_type, jl_apply_type1, jl_apply_type2, jl_array_data,
+    jl_arrayref, jl_arrayset, jl_base_module, jl_fieldref_noalloc, jl_get_global,
+    jl_get_ptls_states, jl_symbol, jl_value_t,
+};
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
+use std::hash::{BuildHasher, Hash};
+use std::ptr::null_mut;
+
+unsafe impl<'frame, 'data, T> Cast<'frame, 'data> for Vec<T>
+where
+    T: Cast<'frame, 'data>,
+{
+    type Output = Vec<T::Output>;
+
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        let array = value.cast::<Array>()?;
+        unsafe {
+            let len = Dimensions::from_array(array.ptr()).size();
+            let mut elements = Vec::with_capacity(len);
+            for idx in 0..len {
+                elements.push(T::cast_element(array, idx)?);
+            }
+
+            Ok(elements)
+        }
+    }
+
+    unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+        let array = value.cast_unchecked::<Array>();
+        let len = Dimensions::from_array(array.ptr()).size();
+        let mut elements = Vec::with_capacity(len);
+        for idx in 0..len {
+            elements.push(T::cast_element_unchecked(array, idx));
+        }
+
+        elements
+    }
+}
+
+unsafe impl<'frame, 'data, K, V, S> Cast<'frame, 'data> for HashMap<K, V, S>
+where
+    K: Cast<'frame, 'data>,
+    K::Output: Eq + Hash,
+    V: Cast<'frame, 'data>,
+    S: BuildHasher + Default,
+{
+    type Output = HashMap<K::Output, V::Output, S>;
+
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        unsafe {
+            if !has_base_type(value, "Dict") {
+                Err(JlrsError::WrongType)?
+            }
+
+            let slots = value.get_field_noalloc("slots")?.cast::<Array>()?;
+            let keys = value.get_field_noalloc("keys")?.cast::<Array>()?;
+            let vals = value.get_field_noalloc("vals")?.cast::<Array>()?;
+            let filled = filled_slots(slots);
+
+            let mut map = HashMap::with_capacity_and_hasher(filled.len(), S::default());
+            for idx in filled {
+                map.insert(K::cast_element(keys, idx)?, V::cast_element(vals, idx)?);
+            }
+
+            Ok(map)
+        }
+    }
+
+    unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+        let keys = dict_field(value, DICT_KEYS);
+        let vals = dict_field(value, DICT_VALS);
+        let filled = filled_slots(dict_field(value, DICT_SLOTS));
+
+        let mut map = HashMap::with_capacity_and_hasher(filled.len(), S::default());
+        for idx in filled {
+            map.insert(
+                K::cast_element_unchecked(keys, idx),
+                V::cast_element_unchecked(vals, idx),
+            );
+        }
+
+        map
+    }
+}
+
+unsafe impl<'frame, 'data, T, S> Cast<'frame, 'data> for HashSet<T, S>
+where
+    T: Cast<'frame, 'data>,
+    T::Output: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Output = HashSet<T::Output, S>;
+
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        unsafe {
+            if !has_base_type(value, "Set") {
+                Err(JlrsError::WrongType)?
+            }
+
+            // A `Set` is a `Dict` whose values are all `nothing`.
+            let dict = value.get_field_noalloc("dict")?;
+            let slots = dict.get_field_noalloc("slots")?.cast::<Array>()?;
+            let keys = dict.get_field_noalloc("keys")?.cast::<Array>()?;
+            let filled = filled_slots(slots);
+
+            let mut set = HashSet::with_capacity_and_hasher(filled.len(), S::default());
+            for idx in filled {
+                set.insert(T::cast_element(keys, idx)?);
+            }
+
+            Ok(set)
+        }
+    }
+
+    unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+        let dict = Value::wrap(jl_fieldref_noalloc(value.ptr(), 0));
+        let keys = dict_field(dict, DICT_KEYS);
+        let filled = filled_slots(dict_field(dict, DICT_SLOTS));
+
+        let mut set = HashSet::with_capacity_and_hasher(filled.len(), S::default());
+        for idx in filled {
+            set.insert(T::cast_element_unchecked(keys, idx));
+        }
+
+        set
+    }
+}
+
+unsafe impl<T: IntoJulia + ElementType> IntoJulia for Vec<T> {
+    unsafe fn into_julia(&self) -> *mut jl_value_t {
+        new_vector(self)
+    }
+}
+
+unsafe impl<T: IntoJulia + ElementType> IntoJulia for &[T] {
+    unsafe fn into_julia(&self) -> *mut jl_value_t {
+        new_vector(self)
+    }
+}
+
+unsafe impl<T: ElementType> ElementType for Vec<T> {
+    unsafe fn element_type() -> *mut jl_value_t {
+        vector_type::<T>()
+    }
+}
+
+unsafe impl<T: ElementType> ElementType for &[T] {
+    unsafe fn element_type() -> *mut jl_value_t {
+        vector_type::<T>()
+    }
+}
+
+// Allocates a `Vector` and stores the elements in it. The vector is rooted while the elements
+// are converted, an element is rooted by the vector as soon as it has been stored.
+unsafe fn new_vector<T: IntoJulia + ElementType>(elements: &[T]) -> *mut jl_value_t {
+    let array = jl_alloc_array_1d(vector_type::<T>(), elements.len());
+    with_root(array.cast(), || {
+        for (idx, element) in elements.iter().enumerate() {
+            jl_arrayset(array, element.into_julia(), idx);
+        }
+    });
+
+    array.cast()
+}
+
+// Creates a `Dict` with the given entries in a nested frame, see `Value::new_dict`.
+pub(crate) unsafe fn new_dict<'frame, 'k, 'v, K, V, I, F>(
+    frame: &mut F,
+    entries: I,
+) -> JlrsResult<CallResult<'frame, 'static>>
+where
+    K: IntoJulia + ElementType + 'k,
+    V: IntoJulia + ElementType + 'v,
+    I: IntoIterator<Item = (&'k K, &'v V)>,
+    F: Frame<'frame>,
+{
+    let setindex = Module::base(Global::new()).function("setindex!")?;
+    new_collection(
+        frame,
+        dict_type::<K, V>(),
+        entries,
+        |frame, dict, (key, value)| {
+            let key = protect(frame, key)?;
+            let value = protect(frame, value)?;
+            setindex.call3(frame, dict, value, key)
+        },
+    )
+}
+
+// Creates a `Set` with the given elements in a nested frame, see `Value::new_set`.
+pub(crate) unsafe fn new_set<'frame, 't, T, I, F>(
+    frame: &mut F,
+    elements: I,
+) -> JlrsResult<CallResult<'frame, 'static>>
+where
+    T: IntoJulia + ElementType + 't,
+    I: IntoIterator<Item = &'t T>,
+    F: Frame<'frame>,
+{
+    let push = Module::base(Global::new()).function("push!")?;
+    new_collection(frame, set_type::<T>(), elements, |frame, set, element| {
+        let element = protect(frame, element)?;
+        push.call2(frame, set, element)
+    })
+}
+
+// Creates an instance of `ty` by calling its constructor without arguments, and calls `insert`
+// for each item in a nested frame with three slots. The collection is rooted in `frame`, if an
+// exception is thrown it's rooted in `frame` too.
+unsafe fn new_collection<'frame, T, I, F, G>(
+    frame: &mut F,
+    ty: *mut jl_value_t,
+    items: I,
+    mut insert: G,
+) -> JlrsResult<CallResult<'frame, 'static>>
+where
+    I: IntoIterator<Item = T>,
+    F: Frame<'frame>,
+    G: for<'nested> FnMut(
+        &mut StaticFrame<'nested, F::U>,
+        Value<'frame, 'static>,
+        T,
+    ) -> JlrsResult<CallResult<'nested, 'static>>,
+{
+    let collection = match Value::wrap(ty).call0(&mut *frame)? {
+        Ok(collection) => collection,
+        Err(exception) => return Ok(Err(exception)),
+    };
+
+    for item in items {
+        let exception = frame.frame(3, |frame| match insert(frame, collection, item)? {
+            Ok(_) => Ok(None),
+            Err(exception) => Ok(Some(exception.ptr())),
+        })?;
+
+        if let Some(exception) = exception {
+            let exception = frame
+                .protect(exception, Internal)
+                .map_err(Into::<Box<_>>::into)?;
+            return Ok(Err(exception));
+        }
+    }
+
+    Ok(Ok(collection))
+}
+
+// Converts `data` to Julia and roots it in `frame`.
+unsafe fn protect<'frame, T, F>(frame: &mut F, data: &T) -> JlrsResult<Value<'frame, 'static>>
+where
+    T: IntoJulia,
+    F: Frame<'frame>,
+{
+    frame
+        .protect(data.into_julia(), Internal)
+        .map_err(Into::into)
+}
+
+unsafe fn vector_type<T: ElementType>() -> *mut jl_value_t {
+    let elty = T::element_type();
+    with_root(elty, || jl_apply_array_type(elty, 1))
+}
+
+pub(crate) unsafe fn dict_type<K: ElementType, V: ElementType>() -> *mut jl_value_t {
+    let key_type = K::element_type();
+    with_root(key_type, || {
+        let value_type = V::element_type();
+        with_root(value_type, || {
+            jl_apply_type2(base_global("Dict\0"), key_type, value_type)
+        })
+    })
+}
+
+pub(crate) unsafe fn set_type<T: ElementType>() -> *mut jl_value_t {
+    let elty = T::element_type();
+    with_root(elty, || jl_apply_type1(base_global("Set\0"), elty))
+}
+
+// Calls `func` while `value` is rooted in a GC frame with one slot, like `JL_GC_PUSH1` does. The
+// frame contains the number of roots, the previous frame, and the root.
+pub(crate) unsafe fn with_root<T, F>(value: *mut jl_value_t, func: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let mut gc_frame: [*mut c_void; 3] = [(1 << 1) as _, null_mut(), value.cast()];
+    gc_frame[1] = (*jl_get_ptls_states()).pgcstack.cast();
+    (*jl_get_ptls_states()).pgcstack = gc_frame.as_mut_ptr().cast();
+
+    let res = func();
+
+    (*jl_get_ptls_states()).pgcstack = gc_frame[1].cast();
+    res
+}
+
+// Returns the global `name` from `Base`, `name` must be null-terminated.
+unsafe fn base_global(name: &str) -> *mut jl_value_t {
+    jl_get_global(jl_base_module, jl_symbol(name.as_ptr().cast()))
+}
+
+// Returns the element at `idx` of `array`, whose elements must be stored as pointers. The
+// element is null if it's undefined.
+pub(crate) unsafe fn pointer_element(array: Array, idx: usize) -> *mut jl_value_t {
+    let data: *mut *mut jl_value_t = jl_array_data(array.ptr().cast()).cast();
+    *data.add(idx)
+}
+
+// Returns the element at `idx` of `array`, whose elements must be stored inline as a `T`.
+pub(crate) unsafe fn inline_element<T: Copy>(array: Array, idx: usize) -> T {
+    let data: *mut T = jl_array_data(array.ptr().cast()).cast();
+    *data.add(idx)
+}
+
+// Boxes the element at `idx` of `array`, whose elements must be stored inline, and calls `func`
+// with the box. The box is rooted until `func` returns.
+pub(crate) unsafe fn with_boxed_element<'frame, 'data, T, F>(
+    array: Array<'frame, 'data>,
+    idx: usize,
+    func: F,
+) -> T
+where
+    F: FnOnce(Value<'frame, 'data>) -> T,
+{
+    let element = jl_arrayref(array.ptr(), idx);
+    with_root(element, || func(Value::wrap(element)))
+}
+
+// Returns true if `value` is an instance of the type `name` from `Base`, regardless of its type
+// parameters.
+unsafe fn has_base_type(value: Value, name: &str) -> bool {
+    let dt = match value.datatype() {
+        Some(dt) => dt,
+        None => return false,
+    };
+
+    match Module::base(Global::new())
+        .global(name)
+        .and_then(|ty| ty.cast::<UnionAll>())
+    {
+        Ok(ty) => ty.base_type().type_name().ptr() == dt.type_name().ptr(),
+        Err(_) => false,
+    }
+}
+
+// The indices of the `slots`, `keys` and `vals` fields of a `Dict`.
+const DICT_SLOTS: usize = 0;
+const DICT_KEYS: usize = 1;
+const DICT_VALS: usize = 2;
+
+// Returns the array in the field `idx` of `dict`, which must be a `Dict`.
+unsafe fn dict_field<'frame, 'data>(
+    dict: Value<'frame, 'data>,
+    idx: usize,
+) -> Array<'frame, 'data> {
+    Value::wrap(jl_fieldref_noalloc(dict.ptr(), idx)).cast_unchecked::<Array>()
+}
+
+// Returns the indices of the slots of a `Dict` that contain an entry.
+unsafe fn filled_slots(slots: Array) -> Vec<usize> {
+    let len = Dimensions::from_array(slots.ptr()).size();
+    let data: *mut u8 = jl_array_data(slots.ptr().cast()).cast();
+
+    (0..len).filter(|&idx| *data.add(idx) == 0x1).collect()
+}
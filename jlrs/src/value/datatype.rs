@@ -20,7 +20,7 @@
 //! [`JuliaTypecheck`]: ../../traits/trait.JuliaTypecheck.html
 
 use crate::error::{JlrsError, JlrsResult};
-use crate::traits::{Cast, JuliaTypecheck};
+use crate::traits::{private::Internal, Cast, Frame, JuliaTypecheck};
 use crate::value::symbol::Symbol;
 use crate::value::type_name::TypeName;
 use crate::value::Value;
@@ -29,10 +29,11 @@ use jl_sys::{
     jl_any_type, jl_code_info_type, jl_datatype_align, jl_datatype_isinlinealloc,
     jl_datatype_nbits, jl_datatype_nfields, jl_datatype_size, jl_datatype_t, jl_datatype_type,
     jl_field_isptr, jl_field_names, jl_field_offset, jl_field_size, jl_get_fieldtypes,
-    jl_globalref_type, jl_gotonode_type, jl_intrinsic_type, jl_is_cpointer_type, jl_isbits,
-    jl_linenumbernode_type, jl_namedtuple_typename, jl_newvarnode_type, jl_phicnode_type,
-    jl_phinode_type, jl_pinode_type, jl_quotenode_type, jl_slotnumber_type, jl_string_type,
-    jl_svec_data, jl_svec_len, jl_typedslot_type, jl_typename_str, jl_upsilonnode_type,
+    jl_globalref_type, jl_gotonode_type, jl_intrinsic_type, jl_is_cpointer_type, jl_isa, jl_isbits,
+    jl_linenumbernode_type, jl_namedtuple_typename, jl_new_structv, jl_newvarnode_type,
+    jl_phicnode_type, jl_phinode_type, jl_pinode_type, jl_quotenode_type, jl_slotnumber_type,
+    jl_string_type, jl_svec_data, jl_svec_len, jl_typedslot_type, jl_typename_str,
+    jl_upsilonnode_type,
 };
 use std::ffi::CStr;
 use std::fmt::{Debug, Formatter, Result as FmtResult};
@@ -246,6 +247,46 @@ impl<'frame> DataType<'frame> {
     pub fn has_concrete_subtype(self) -> bool {
         unsafe { (&*self.ptr()).has_concrete_subtype != 0 }
     }
+
+    /// Create a new instance of this type with the given field values, this takes one slot on
+    /// the GC stack. The type must be concrete and a value must be provided for every field.
+    /// Returns an error if this type isn't concrete, the number of values doesn't match the
+    /// number of fields, a value isn't an instance of the type of its field, or if no space is
+    /// left on the stack.
+    pub fn instantiate<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        values: V,
+    ) -> JlrsResult<Value<'fr, 'borrow>>
+    where
+        V: AsRef<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        unsafe {
+            if !self.is_concrete_type() {
+                Err(JlrsError::NotConcrete(self.name().into()))?;
+            }
+
+            let values = values.as_ref();
+            let field_types = self.field_types();
+            if values.len() != field_types.len() {
+                Err(JlrsError::WrongFieldCount(field_types.len(), values.len()))?;
+            }
+
+            for (value, ty) in values.iter().zip(field_types.iter()) {
+                if jl_isa(value.ptr(), ty.ptr()) == 0 {
+                    Err(JlrsError::NotSubtype)?;
+                }
+            }
+
+            let mut values: Vec<_> = values.iter().map(|value| value.ptr()).collect();
+            let value = jl_new_structv(self.ptr(), values.as_mut_ptr(), values.len() as _);
+            frame
+                .protect(value, Internal)
+                .map_err(Into::into)
+                .map(|value| Value::wrap(value.ptr()))
+        }
+    }
 }
 
 impl<'frame> Into<Value<'frame, 'static>> for DataType<'frame> {
@@ -284,7 +325,10 @@ pub struct Any;
 impl_julia_typecheck!(Any, jl_any_type);
 
 /// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is a named tuple.
+/// a value of this type is a named tuple. Named tuples can be accessed with the [`NamedTuple`]
+/// wrapper from the `named_tuple` module.
+///
+/// [`NamedTuple`]: ../named_tuple/struct.NamedTuple.html
 pub struct NamedTuple;
 
 unsafe impl JuliaTypecheck for NamedTuple {
@@ -380,48 +424,75 @@ unsafe impl JuliaTypecheck for Slot {
 pub struct GlobalRef;
 impl_julia_typecheck!(GlobalRef, jl_globalref_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is a Goto node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is a Goto node. It can be accessed with the [`GotoNode`] wrapper from the
+/// `ir` module.
+///
+/// [`GotoNode`]: ../ir/struct.GotoNode.html
 pub struct GotoNode;
 impl_julia_typecheck!(GotoNode, jl_gotonode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is a Pi node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is a Pi node. It can be accessed with the [`PiNode`] wrapper from the `ir`
+/// module.
+///
+/// [`PiNode`]: ../ir/struct.PiNode.html
 pub struct PiNode;
 impl_julia_typecheck!(PiNode, jl_pinode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is a Phi node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is a Phi node. It can be accessed with the [`PhiNode`] wrapper from the `ir`
+/// module.
+///
+/// [`PhiNode`]: ../ir/struct.PhiNode.html
 pub struct PhiNode;
 impl_julia_typecheck!(PhiNode, jl_phinode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is a PhiC node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is a PhiC node. It can be accessed with the [`PhiCNode`] wrapper from the
+/// `ir` module.
+///
+/// [`PhiCNode`]: ../ir/struct.PhiCNode.html
 pub struct PhiCNode;
 impl_julia_typecheck!(PhiCNode, jl_phicnode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is an Upsilon node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is an Upsilon node. It can be accessed with the [`UpsilonNode`] wrapper from
+/// the `ir` module.
+///
+/// [`UpsilonNode`]: ../ir/struct.UpsilonNode.html
 pub struct UpsilonNode;
 impl_julia_typecheck!(UpsilonNode, jl_upsilonnode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is a Quote node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is a Quote node. It can be accessed with the [`QuoteNode`] wrapper from the
+/// `ir` module.
+///
+/// [`QuoteNode`]: ../ir/struct.QuoteNode.html
 pub struct QuoteNode;
 impl_julia_typecheck!(QuoteNode, jl_quotenode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is an NewVar node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is an NewVar node. It can be accessed with the [`NewVarNode`] wrapper from
+/// the `ir` module.
+///
+/// [`NewVarNode`]: ../ir/struct.NewVarNode.html
 pub struct NewVarNode;
 impl_julia_typecheck!(NewVarNode, jl_newvarnode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is a Line node.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is a Line node. It can be accessed with the [`LineNumberNode`] wrapper from
+/// the `ir` module.
+///
+/// [`LineNumberNode`]: ../ir/struct.LineNumberNode.html
 pub struct LineNode;
 impl_julia_typecheck!(LineNode, jl_linenumbernode_type);
 
-/// A typecheck that can be used in combination with `DataType::is`. This method returns true if
-/// a value of this type is code info.
+/// A typecheck that can be used in combination with `DataType::is`. This method returns true if a
+/// value of this type is code info. It can be accessed with the [`CodeInfo`] wrapper from the
+/// `code_info` module.
+///
+/// [`CodeInfo`]: ../code_info/struct.CodeInfo.html
 pub struct CodeInfo;
 impl_julia_typecheck!(CodeInfo, jl_code_info_type);
 
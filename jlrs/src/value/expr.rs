@@ -1,12 +1,26 @@
 //! Support for values with the `Core.Expr` type.
+//!
+//! Expressions can be built from Rust with [`Expr::new`], the nodes that commonly appear in them
+//! can be created with [`Expr::quote_node`], [`Expr::line_number_node`] and
+//! [`Expr::global_ref`]. Code can also be parsed with [`Expr::parse`]. An expression can be
+//! evaluated by calling [`Module::eval`].
+//!
+//! [`Expr::new`]: struct.Expr.html#method.new
+//! [`Expr::quote_node`]: struct.Expr.html#method.quote_node
+//! [`Expr::line_number_node`]: struct.Expr.html#method.line_number_node
+//! [`Expr::global_ref`]: struct.Expr.html#method.global_ref
+//! [`Expr::parse`]: struct.Expr.html#method.parse
+//! [`Module::eval`]: ../module/struct.Module.html#method.eval
 
 use super::array::Array;
+use super::module::Module;
 use super::symbol::Symbol;
-use super::Value;
+use super::{CallResult, Value};
 use crate::error::{JlrsError, JlrsResult};
-use crate::traits::Cast;
+use crate::global::Global;
+use crate::traits::{private::Internal, Cast, Frame, TemporarySymbol};
 use crate::{impl_julia_type, impl_julia_typecheck, impl_valid_layout};
-use jl_sys::{jl_expr_t, jl_expr_type};
+use jl_sys::{jl_expr_t, jl_expr_type, jl_module_globalref, jl_nothing};
 use std::marker::PhantomData;
 
 /// A compound expression in Julia ASTs.
@@ -24,6 +38,124 @@ impl<'frame> Expr<'frame> {
         self.0
     }
 
+    /// Create a new expression, `Expr(head, args...)`. This takes one slot on the GC stack.
+    /// Returns the expression if no exception is thrown, the exception if one is, or an error if
+    /// no space is left on the stack. The expression can be cast to an `Expr`.
+    pub fn new<'value, N, V, F>(
+        frame: &mut F,
+        head: N,
+        args: V,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        N: TemporarySymbol,
+        V: AsRef<[Value<'value, 'static>]>,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let head = head.temporary_symbol(Internal);
+            let mut params = Vec::with_capacity(args.as_ref().len() + 1);
+            params.push(head.into());
+            params.extend_from_slice(args.as_ref());
+
+            Module::core(Global::new())
+                .global("Expr")?
+                .call(frame, params)
+        }
+    }
+
+    /// Create a new `QuoteNode` that contains `value`, this can be used to insert a value into
+    /// an expression without it being evaluated. This takes one slot on the GC stack. Returns the
+    /// node if no exception is thrown, the exception if one is, or an error if no space is left
+    /// on the stack.
+    pub fn quote_node<F>(
+        frame: &mut F,
+        value: Value<'_, 'static>,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        F: Frame<'frame>,
+    {
+        unsafe {
+            Module::core(Global::new())
+                .global("QuoteNode")?
+                .call1(frame, value)
+        }
+    }
+
+    /// Create a new `LineNumberNode` for line `line` in `file`, if no file is provided it's set
+    /// to `nothing`. This takes one slot on the GC stack, a nested frame with two slots is used
+    /// to create it. Returns the node if no exception is thrown, the exception if one is, or an
+    /// error if no space is left on the stack.
+    pub fn line_number_node<N, F>(
+        frame: &mut F,
+        line: isize,
+        file: Option<N>,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        N: TemporarySymbol,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let node = frame.frame(2, |frame| {
+                let line = Value::new(&mut *frame, line)?;
+                let file = match file {
+                    Some(file) => file.temporary_symbol(Internal).into(),
+                    None => Value::wrap(jl_nothing),
+                };
+
+                let node = Module::core(Global::new())
+                    .global("LineNumberNode")?
+                    .call2(&mut *frame, line, file)?;
+
+                Ok(node.map(|v| v.ptr()).map_err(|e| e.ptr()))
+            })?;
+
+            let protected = match node {
+                Ok(node) => frame.protect(node, Internal).map(Ok),
+                Err(exc) => frame.protect(exc, Internal).map(Err),
+            };
+
+            protected.map_err(Into::<Box<_>>::into)
+        }
+    }
+
+    /// Create a new `GlobalRef` to the global `name` in `module`. This takes one slot on the GC
+    /// stack. Returns an error if there's no space left on the stack.
+    pub fn global_ref<N, F>(
+        frame: &mut F,
+        module: Module,
+        name: N,
+    ) -> JlrsResult<Value<'frame, 'static>>
+    where
+        N: TemporarySymbol,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let name = name.temporary_symbol(Internal);
+            let global_ref = jl_module_globalref(module.ptr(), name.ptr());
+            frame
+                .protect(global_ref, Internal)
+                .map_err(Into::<Box<_>>::into)
+        }
+    }
+
+    /// Parse `code` into an expression by calling `Meta.parse`. The result isn't necessarily an
+    /// `Expr`, code like `"x"` or `"1"` is parsed into a `Symbol` or an `Int`. This takes two
+    /// slots on the GC stack. Returns the result if no exception is thrown, the exception if one
+    /// is, or an error if no space is left on the stack.
+    pub fn parse<C, F>(frame: &mut F, code: C) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        C: AsRef<str>,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let code = Value::new(&mut *frame, code.as_ref())?;
+            Module::base(Global::new())
+                .submodule("Meta")?
+                .function("parse")?
+                .call1(frame, code)
+        }
+    }
+
     /// Returns the head of the expression.
     pub fn head(self) -> Symbol<'frame> {
         unsafe { Symbol::wrap((&*self.ptr()).head) }
@@ -0,0 +1,259 @@
+//! Support for the nodes that appear in lowered and typed Julia code.
+//!
+//! The statements of a [`CodeInfo`] are either expressions or one of the nodes defined in this
+//! module, like `SSAValue` and `GotoNode`. Every node can be cast from a `Value` and exposes its
+//! fields.
+//!
+//! [`CodeInfo`]: ../code_info/struct.CodeInfo.html
+
+use super::array::Array;
+use super::Value;
+use crate::error::{JlrsError, JlrsResult};
+use crate::traits::{private::Internal, Cast, TemporarySymbol};
+use crate::{impl_julia_type, impl_julia_typecheck, impl_valid_layout};
+use jl_sys::{
+    jl_field_index, jl_field_offset, jl_fieldref_noalloc, jl_gotonode_type, jl_linenumbernode_type,
+    jl_newvarnode_type, jl_phicnode_type, jl_phinode_type, jl_pinode_type, jl_quotenode_type,
+    jl_ssavalue_type, jl_typeof, jl_upsilonnode_type, jl_value_t,
+};
+use std::marker::PhantomData;
+
+macro_rules! impl_ir_node {
+    ($type:ident, $jl_type:expr, $err:expr) => {
+        impl<'frame> $type<'frame> {
+            pub(crate) unsafe fn wrap(node: *mut jl_value_t) -> Self {
+                $type(node, PhantomData)
+            }
+
+            #[doc(hidden)]
+            pub unsafe fn ptr(self) -> *mut jl_value_t {
+                self.0
+            }
+        }
+
+        impl<'frame> Into<Value<'frame, 'static>> for $type<'frame> {
+            fn into(self) -> Value<'frame, 'static> {
+                unsafe { Value::wrap(self.ptr()) }
+            }
+        }
+
+        unsafe impl<'frame, 'data> Cast<'frame, 'data> for $type<'frame> {
+            type Output = Self;
+            fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+                if value.is::<Self::Output>() {
+                    return unsafe { Ok(Self::cast_unchecked(value)) };
+                }
+
+                Err($err)?
+            }
+
+            unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+                Self::wrap(value.ptr())
+            }
+        }
+
+        impl_julia_typecheck!($type<'frame>, $jl_type, 'frame);
+        impl_julia_type!($type<'frame>, $jl_type, 'frame);
+        impl_valid_layout!($type<'frame>, 'frame);
+    };
+}
+
+/// A reference to the result of the statement at `id` in the same `CodeInfo`.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct SSAValue<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> SSAValue<'frame> {
+    /// The index of the statement, starting at 1.
+    pub fn id(self) -> isize {
+        unsafe { bits_field(self.ptr(), "id") }
+    }
+}
+
+impl_ir_node!(SSAValue, jl_ssavalue_type, JlrsError::NotAnSSAValue);
+
+/// An unconditional jump to the statement at `label`.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct GotoNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> GotoNode<'frame> {
+    /// The index of the statement that is jumped to, starting at 1.
+    pub fn label(self) -> isize {
+        unsafe { bits_field(self.ptr(), "label") }
+    }
+}
+
+impl_ir_node!(GotoNode, jl_gotonode_type, JlrsError::NotAGotoNode);
+
+/// Asserts that `value` has type `typ`, the result of this assertion is used in place of
+/// `value` by the statements that follow it.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PiNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> PiNode<'frame> {
+    /// The value whose type is asserted.
+    pub fn value(self) -> Value<'frame, 'static> {
+        unsafe { defined_field(self.ptr(), "val") }
+    }
+
+    /// The asserted type.
+    pub fn typ(self) -> Value<'frame, 'static> {
+        unsafe { defined_field(self.ptr(), "typ") }
+    }
+}
+
+impl_ir_node!(PiNode, jl_pinode_type, JlrsError::NotAPiNode);
+
+/// Selects a value depending on the edge control flow arrived from.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PhiNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> PhiNode<'frame> {
+    /// The indices of the statements control flow can arrive from.
+    pub fn edges(self) -> Array<'frame, 'static> {
+        unsafe { Array::wrap(defined_field(self.ptr(), "edges").ptr().cast()) }
+    }
+
+    /// The value that is selected for each edge. An element is undefined if the value is
+    /// undefined on that edge.
+    pub fn values(self) -> Array<'frame, 'static> {
+        unsafe { Array::wrap(defined_field(self.ptr(), "values").ptr().cast()) }
+    }
+}
+
+impl_ir_node!(PhiNode, jl_phinode_type, JlrsError::NotAPhiNode);
+
+/// Selects a value depending on the `UpsilonNode` that was executed last, this is used for values
+/// that are live across the edges of a `try`/`catch` block.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PhiCNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> PhiCNode<'frame> {
+    /// The `UpsilonNode`s this node selects from, as SSA values.
+    pub fn values(self) -> Array<'frame, 'static> {
+        unsafe { Array::wrap(defined_field(self.ptr(), "values").ptr().cast()) }
+    }
+}
+
+impl_ir_node!(PhiCNode, jl_phicnode_type, JlrsError::NotAPhiCNode);
+
+/// Sets the value a `PhiCNode` selects if this node is the last one that was executed.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct UpsilonNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> UpsilonNode<'frame> {
+    /// The value that is set, `None` if it's undefined.
+    pub fn value(self) -> Option<Value<'frame, 'static>> {
+        unsafe { pointer_field(self.ptr(), "val") }
+    }
+}
+
+impl_ir_node!(
+    UpsilonNode,
+    jl_upsilonnode_type,
+    JlrsError::NotAnUpsilonNode
+);
+
+/// A value that is used as-is rather than being evaluated.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct QuoteNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> QuoteNode<'frame> {
+    /// The quoted value.
+    pub fn value(self) -> Value<'frame, 'static> {
+        unsafe { defined_field(self.ptr(), "value") }
+    }
+}
+
+impl_ir_node!(QuoteNode, jl_quotenode_type, JlrsError::NotAQuoteNode);
+
+/// Marks the point where a new variable is introduced in a slot, the slot is undefined until
+/// it's assigned.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct NewVarNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> NewVarNode<'frame> {
+    /// The number of the slot, starting at 1.
+    pub fn slot(self) -> isize {
+        unsafe { bits_field(self.ptr(), "slot") }
+    }
+}
+
+impl_ir_node!(NewVarNode, jl_newvarnode_type, JlrsError::NotANewVarNode);
+
+/// The line and file the statements that follow it originate from.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct LineNumberNode<'frame>(*mut jl_value_t, PhantomData<&'frame ()>);
+
+impl<'frame> LineNumberNode<'frame> {
+    /// The line number.
+    pub fn line(self) -> isize {
+        unsafe { bits_field(self.ptr(), "line") }
+    }
+
+    /// The file, this is either a `Symbol` or `nothing`.
+    pub fn file(self) -> Value<'frame, 'static> {
+        unsafe { defined_field(self.ptr(), "file") }
+    }
+}
+
+impl_ir_node!(
+    LineNumberNode,
+    jl_linenumbernode_type,
+    JlrsError::NotALineNumberNode
+);
+
+// Returns the field `name` of `value`, which must be stored as a pointer. Returns `None` if the
+// field is undefined.
+pub(crate) unsafe fn pointer_field<'frame>(
+    value: *mut jl_value_t,
+    name: &str,
+) -> Option<Value<'frame, 'static>> {
+    let idx = field_index(value, name);
+    let field = jl_fieldref_noalloc(value, idx);
+    if field.is_null() {
+        None
+    } else {
+        Some(Value::wrap(field))
+    }
+}
+
+// Returns the field `name` of `value`, which must be stored as a pointer and be initialized by
+// every constructor of its type.
+pub(crate) unsafe fn defined_field<'frame>(
+    value: *mut jl_value_t,
+    name: &str,
+) -> Value<'frame, 'static> {
+    let idx = field_index(value, name);
+    Value::wrap(jl_fieldref_noalloc(value, idx))
+}
+
+// Returns the field `name` of `value`, which must be stored inline and start with data of type
+// `T`.
+pub(crate) unsafe fn bits_field<T: Copy>(value: *mut jl_value_t, name: &str) -> T {
+    let idx = field_index(value, name);
+    let offset = jl_field_offset(jl_typeof(value).cast(), idx as _);
+    *(value.cast::<u8>().add(offset as usize).cast())
+}
+
+// The names of the fields are fixed by Julia, so a missing field means these bindings don't
+// match the version of Julia that is used.
+unsafe fn field_index<N: TemporarySymbol>(value: *mut jl_value_t, name: N) -> usize {
+    let symbol = name.temporary_symbol(Internal);
+    let idx = jl_field_index(jl_typeof(value).cast(), symbol.ptr(), 0);
+    assert!(
+        idx >= 0,
+        "{} has no field named {}",
+        Value::wrap(value).type_name(),
+        Into::<String>::into(symbol)
+    );
+    idx as usize
+}
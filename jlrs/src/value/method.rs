@@ -5,7 +5,7 @@
 //!
 //! [`julia.h`]: https://github.com/JuliaLang/julia/blob/96786e22ccabfdafd073122abb1fb69cea921e17/src/julia.h#L273
 
-use super::array::Array;
+use super::array::{Array, Dimensions};
 use super::method_instance::MethodInstance;
 use super::module::Module;
 use super::simple_vector::SimpleVector;
@@ -14,7 +14,7 @@ use super::Value;
 use crate::error::{JlrsError, JlrsResult};
 use crate::traits::Cast;
 use crate::{impl_julia_type, impl_julia_typecheck, impl_valid_layout};
-use jl_sys::{jl_method_t, jl_method_type};
+use jl_sys::{jl_array_data, jl_array_t, jl_method_t, jl_method_type};
 use std::marker::PhantomData;
 
 /// This type describes a single method definition, and stores data shared by the specializations
@@ -187,6 +187,47 @@ impl<'frame> Method<'frame> {
     }
 }
 
+/// An iterator over the methods of a function, returned by [`Value::methods`].
+///
+/// [`Value::methods`]: ../struct.Value.html#method.methods
+pub struct Methods<'frame> {
+    methods: Array<'frame, 'static>,
+    idx: usize,
+    len: usize,
+}
+
+impl<'frame> Methods<'frame> {
+    pub(crate) unsafe fn new(methods: *mut jl_array_t) -> Self {
+        Methods {
+            methods: Array::wrap(methods),
+            idx: 0,
+            len: Dimensions::from_array(methods).size(),
+        }
+    }
+}
+
+impl<'frame> Iterator for Methods<'frame> {
+    type Item = Method<'frame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.len {
+            return None;
+        }
+
+        unsafe {
+            let data: *mut *mut jl_method_t = jl_array_data(self.methods.ptr().cast()).cast();
+            let method = Method::wrap(*data.add(self.idx));
+            self.idx += 1;
+            Some(method)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.len - self.idx;
+        (n, Some(n))
+    }
+}
+
 impl<'frame> Into<Value<'frame, 'static>> for Method<'frame> {
     fn into(self) -> Value<'frame, 'static> {
         unsafe { Value::wrap(self.ptr().cast()) }
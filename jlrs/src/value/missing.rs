@@ -0,0 +1,63 @@
+//! Support for `missing`, the singleton instance of `Base.Missing`.
+//!
+//! `missing` is used to represent missing data. [`Missing`] can be used with [`Value::is`] and
+//! [`Value::cast`], and converted to Julia with [`Value::new`]. When an `Option` is cast, both
+//! `nothing` and `missing` are converted to `None`.
+//!
+//! [`Missing`]: struct.Missing.html
+//! [`Value::is`]: ../struct.Value.html#method.is
+//! [`Value::cast`]: ../struct.Value.html#method.cast
+//! [`Value::new`]: ../struct.Value.html#method.new
+
+use super::Value;
+use crate::error::{JlrsError, JlrsResult};
+use crate::global::Global;
+use crate::impl_julia_typecheck;
+use crate::traits::{Cast, IntoJulia, JuliaType};
+use jl_sys::{jl_base_module, jl_datatype_t, jl_get_global, jl_symbol, jl_value_t};
+
+/// The value `missing`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Missing;
+
+impl Missing {
+    /// Returns `missing` as a `Value`. Because `missing` is a singleton this takes no slot on
+    /// the GC stack.
+    pub fn value<'base>(_: Global<'base>) -> Value<'base, 'static> {
+        unsafe { Value::wrap(missing()) }
+    }
+}
+
+unsafe impl IntoJulia for Missing {
+    unsafe fn into_julia(&self) -> *mut jl_value_t {
+        missing()
+    }
+}
+
+unsafe impl JuliaType for Missing {
+    unsafe fn julia_type() -> *mut jl_datatype_t {
+        jl_get_global(jl_base_module, jl_symbol("Missing\0".as_ptr().cast())).cast()
+    }
+}
+
+impl_julia_typecheck!(Missing);
+
+unsafe impl<'frame, 'data> Cast<'frame, 'data> for Missing {
+    type Output = Self;
+
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        if value.is::<Missing>() {
+            return unsafe { Ok(Self::cast_unchecked(value)) };
+        }
+
+        Err(JlrsError::WrongType)?
+    }
+
+    unsafe fn cast_unchecked(_: Value<'frame, 'data>) -> Self::Output {
+        Missing
+    }
+}
+
+unsafe fn missing() -> *mut jl_value_t {
+    jl_get_global(jl_base_module, jl_symbol("missing\0".as_ptr().cast()))
+}
@@ -2,13 +2,13 @@
 
 use crate::error::{JlrsError, JlrsResult};
 use crate::global::Global;
-use crate::traits::{private::Internal, Cast, TemporarySymbol};
+use crate::traits::{private::Internal, Cast, Frame, TemporarySymbol};
 use crate::value::symbol::Symbol;
-use crate::value::Value;
+use crate::value::{CallResult, Value};
 use crate::{impl_julia_type, impl_julia_typecheck, impl_valid_layout};
 use jl_sys::{
-    jl_base_module, jl_core_module, jl_get_global, jl_main_module, jl_module_t, jl_module_type,
-    jl_set_const, jl_set_global, jl_typeis,
+    jl_base_module, jl_call2, jl_core_module, jl_exception_occurred, jl_get_global, jl_main_module,
+    jl_module_t, jl_module_type, jl_set_const, jl_set_global, jl_typeis,
 };
 use std::fmt::{Debug, Formatter, Result as FmtResult};
 use std::marker::PhantomData;
@@ -85,6 +85,40 @@ impl<'base> Module<'base> {
         unsafe { Module::wrap(jl_base_module) }
     }
 
+    /// Load the package named `name` by calling `Base.require`, and return its root module. The
+    /// package is looked up in the active project, see [`Julia::activate_project`]. Unlike
+    /// `using` and `import`, this doesn't make the package or its exported names available in
+    /// `Main`, use [`Julia::using`] for that. If the package has already been loaded, it's
+    /// returned immediately.
+    ///
+    /// Returns an error if the package can't be loaded.
+    ///
+    /// [`Julia::activate_project`]: ../../struct.Julia.html#method.activate_project
+    /// [`Julia::using`]: ../../struct.Julia.html#method.using
+    pub fn require<N>(global: Global<'base>, name: N) -> JlrsResult<Self>
+    where
+        N: TemporarySymbol,
+    {
+        unsafe {
+            let symbol = name.temporary_symbol(Internal);
+            let require = Module::base(global).function("require")?;
+            let main = Module::main(global);
+
+            let module = jl_call2(require.ptr(), main.ptr().cast(), symbol.ptr().cast());
+            let exc = jl_exception_occurred();
+            if !exc.is_null() {
+                let err_type = Value::wrap(exc).type_name().into();
+                Err(JlrsError::LoadError(symbol.into(), err_type))?;
+            }
+
+            if !jl_typeis(module, jl_module_type) {
+                Err(JlrsError::NotAModule(symbol.into()))?;
+            }
+
+            Ok(Module::wrap(module.cast()))
+        }
+    }
+
     /// Returns the submodule named `name` relative to this module. You have to visit this level
     /// by level: you can't access `Main.A.B` by calling this function with `"A.B"`, but have to
     /// access `A` first and then `B`.
@@ -168,6 +202,28 @@ impl<'base> Module<'base> {
         }
     }
 
+    /// Evaluate `expr` in this module by calling `Core.eval`. The expression can be created with
+    /// [`Expr::new`] or [`Expr::parse`]. This takes one slot on the GC stack. Returns the result
+    /// if no exception is thrown, the exception if one is, or an error if no space is left on the
+    /// stack.
+    ///
+    /// [`Expr::new`]: ../expr/struct.Expr.html#method.new
+    /// [`Expr::parse`]: ../expr/struct.Expr.html#method.parse
+    pub fn eval<'frame, F>(
+        self,
+        frame: &mut F,
+        expr: Value<'_, 'static>,
+    ) -> JlrsResult<CallResult<'frame, 'static>>
+    where
+        F: Frame<'frame>,
+    {
+        unsafe {
+            Module::core(Global::new())
+                .function("eval")?
+                .call2(frame, self.into(), expr)
+        }
+    }
+
     /// Returns the function named `name` in this module. Note that all globals defined within the
     /// module will be successfully resolved into a function; Julia will throw an exception if you
     /// try to call something that isn't a function. This means that this method is just an alias
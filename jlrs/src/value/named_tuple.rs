@@ -0,0 +1,303 @@
+//! Support for values with the `Core.NamedTuple` type.
+//!
+//! A `NamedTuple` can be created from `(name, Value)` pairs with [`NamedTuple::new`], or from
+//! the fields of a struct with [`NamedTuple::from_struct`] and [`NamedTuple::from_data`]. The
+//! latter can be used with structs that derive `IntoJulia`.
+//!
+//! [`NamedTuple::new`]: struct.NamedTuple.html#method.new
+//! [`NamedTuple::from_struct`]: struct.NamedTuple.html#method.from_struct
+//! [`NamedTuple::from_data`]: struct.NamedTuple.html#method.from_data
+
+use super::array::Array;
+use super::collections::pointer_element;
+use super::symbol::Symbol;
+use super::Value;
+use crate::error::{JlrsError, JlrsResult};
+use crate::frame::Output;
+use crate::global::Global;
+use crate::impl_valid_layout;
+use crate::traits::{private::Internal, Cast, Frame, IntoJulia, JuliaTypecheck, TemporarySymbol};
+use crate::value::datatype::{self, DataType};
+use crate::value::module::Module;
+use jl_sys::{jl_call1, jl_exception_occurred, jl_fieldref_noalloc, jl_value_t};
+use std::marker::PhantomData;
+
+/// A Julia `NamedTuple`. Its fields can be accessed by name with [`NamedTuple::get`] and
+/// iterated over as `(Symbol, Value)` pairs with [`NamedTuple::iter`].
+///
+/// [`NamedTuple::get`]: struct.NamedTuple.html#method.get
+/// [`NamedTuple::iter`]: struct.NamedTuple.html#method.iter
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct NamedTuple<'frame, 'data>(
+    *mut jl_value_t,
+    PhantomData<&'frame ()>,
+    PhantomData<&'data ()>,
+);
+
+impl<'frame, 'data> NamedTuple<'frame, 'data> {
+    pub(crate) unsafe fn wrap(named_tuple: *mut jl_value_t) -> Self {
+        NamedTuple(named_tuple, PhantomData, PhantomData)
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn ptr(self) -> *mut jl_value_t {
+        self.0
+    }
+
+    /// Create a new `NamedTuple` from `(name, value)` pairs. This takes one slot on the GC
+    /// stack, a nested frame with three slots is used to create it. Returns an error if a name
+    /// is used more than once or there's not enough space on the stack.
+    pub fn new<N, F>(frame: &mut F, pairs: &[(N, Value<'_, 'data>)]) -> JlrsResult<Self>
+    where
+        N: TemporarySymbol,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let names: Vec<Symbol> = pairs
+                .iter()
+                .map(|(name, _)| name.temporary_symbol(Internal))
+                .collect();
+            let values: Vec<Value> = pairs.iter().map(|&(_, value)| value).collect();
+
+            let output = frame.output()?;
+            new_output(frame, output, &names, &values)
+        }
+    }
+
+    /// Create a new `NamedTuple` from the fields of `value`, the names of the fields are used as
+    /// the names of the `NamedTuple`. If `value` is already a `NamedTuple`, it's returned
+    /// unchanged. This takes one slot on the GC stack, a nested frame is used to create it.
+    pub fn from_struct<F>(frame: &mut F, value: Value<'_, 'data>) -> JlrsResult<Self>
+    where
+        F: Frame<'frame>,
+    {
+        unsafe {
+            if value.is::<datatype::NamedTuple>() {
+                let value = frame
+                    .protect(value.ptr(), Internal)
+                    .map_err(Into::<Box<_>>::into)?;
+                return Ok(NamedTuple::wrap(value.ptr()));
+            }
+
+            let output = frame.output()?;
+            frame.dynamic_frame(|frame| {
+                let names = value.field_names();
+                let mut values = Vec::with_capacity(names.len());
+                for idx in 0..names.len() {
+                    values.push(value.get_nth_field(frame, idx)?);
+                }
+
+                new_output(frame, output, names, &values)
+            })
+        }
+    }
+
+    /// Convert `data` to a Julia value and create a new `NamedTuple` from its fields, this is
+    /// useful for structs that derive `IntoJulia`. This takes one slot on the GC stack, a nested
+    /// frame is used to create it.
+    pub fn from_data<T, F>(frame: &mut F, data: T) -> JlrsResult<NamedTuple<'frame, 'static>>
+    where
+        T: IntoJulia,
+        F: Frame<'frame>,
+    {
+        unsafe {
+            let named_tuple = frame.dynamic_frame(|frame| {
+                let value = Value::new(&mut *frame, data)?;
+                Ok(NamedTuple::from_struct(frame, value)?.ptr())
+            })?;
+
+            let named_tuple = frame
+                .protect(named_tuple, Internal)
+                .map_err(Into::<Box<_>>::into)?;
+            Ok(NamedTuple::wrap(named_tuple.ptr()))
+        }
+    }
+
+    /// Returns the number of fields of this `NamedTuple`.
+    pub fn len(self) -> usize {
+        self.as_value().n_fields()
+    }
+
+    /// Returns `true` if this `NamedTuple` has no fields.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the names of the fields of this `NamedTuple`.
+    pub fn names(self) -> &'frame [Symbol<'frame>] {
+        self.as_value().field_names()
+    }
+
+    /// Returns `true` if this `NamedTuple` has a field named `name`.
+    pub fn contains<N>(self, name: N) -> bool
+    where
+        N: TemporarySymbol,
+    {
+        unsafe {
+            let name = name.temporary_symbol(Internal);
+            self.names().iter().any(|n| n.ptr() == name.ptr())
+        }
+    }
+
+    /// Returns the field named `name`. This takes one slot on the GC stack. Returns an error if
+    /// the field doesn't exist or there's no space left on the stack.
+    pub fn get<'fr, N, F>(self, frame: &mut F, name: N) -> JlrsResult<Value<'fr, 'data>>
+    where
+        N: TemporarySymbol,
+        F: Frame<'fr>,
+    {
+        self.as_value().get_field(frame, name)
+    }
+
+    /// Returns an iterator over the fields of this `NamedTuple` as `(Symbol, Value)` pairs.
+    /// Every field that is returned takes one slot on the GC stack.
+    pub fn iter<'borrow, 'fr, F>(
+        self,
+        frame: &'borrow mut F,
+    ) -> Iter<'borrow, 'frame, 'fr, 'data, F>
+    where
+        F: Frame<'fr>,
+    {
+        Iter {
+            named_tuple: self,
+            frame,
+            idx: 0,
+            _frame: PhantomData,
+        }
+    }
+
+    /// Returns this `NamedTuple` as a `Value`.
+    pub fn as_value(self) -> Value<'frame, 'data> {
+        unsafe { Value::wrap(self.ptr()) }
+    }
+}
+
+/// An iterator over the fields of a [`NamedTuple`], returned by [`NamedTuple::iter`].
+///
+/// [`NamedTuple`]: struct.NamedTuple.html
+/// [`NamedTuple::iter`]: struct.NamedTuple.html#method.iter
+pub struct Iter<'borrow, 'frame, 'fr, 'data, F> {
+    named_tuple: NamedTuple<'frame, 'data>,
+    frame: &'borrow mut F,
+    idx: usize,
+    _frame: PhantomData<&'fr ()>,
+}
+
+impl<'borrow, 'frame, 'fr, 'data, F> Iterator for Iter<'borrow, 'frame, 'fr, 'data, F>
+where
+    F: Frame<'fr>,
+{
+    type Item = JlrsResult<(Symbol<'frame>, Value<'fr, 'data>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let names = self.named_tuple.names();
+        if self.idx >= names.len() {
+            return None;
+        }
+
+        let name = names[self.idx];
+        let value = self
+            .named_tuple
+            .as_value()
+            .get_nth_field(self.frame, self.idx);
+        self.idx += 1;
+        Some(value.map(|value| (name, value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.named_tuple.len() - self.idx;
+        (n, Some(n))
+    }
+}
+
+impl<'frame, 'data> Into<Value<'frame, 'data>> for NamedTuple<'frame, 'data> {
+    fn into(self) -> Value<'frame, 'data> {
+        self.as_value()
+    }
+}
+
+unsafe impl<'frame, 'data> Cast<'frame, 'data> for NamedTuple<'frame, 'data> {
+    type Output = Self;
+    fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+        if value.is::<Self::Output>() {
+            return unsafe { Ok(Self::cast_unchecked(value)) };
+        }
+
+        Err(JlrsError::NotANamedTuple)?
+    }
+
+    unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+        Self::wrap(value.ptr())
+    }
+
+    // A field that is stored inline would have to be boxed, the box is not rooted.
+    fn cast_field(value: Value<'frame, 'data>, idx: usize) -> JlrsResult<Self::Output> {
+        unsafe { Self::cast(value.get_nth_field_noalloc(idx)?) }
+    }
+
+    unsafe fn cast_field_unchecked(value: Value<'frame, 'data>, idx: usize) -> Self::Output {
+        Self::wrap(jl_fieldref_noalloc(value.ptr(), idx))
+    }
+
+    // An element that is stored inline would have to be boxed, the box is not rooted.
+    unsafe fn cast_element(array: Array<'frame, 'data>, idx: usize) -> JlrsResult<Self::Output> {
+        if array.is_inline_array() {
+            Err(JlrsError::Inline)?
+        }
+
+        Self::cast(Value::wrap(pointer_element(array, idx)))
+    }
+
+    unsafe fn cast_element_unchecked(array: Array<'frame, 'data>, idx: usize) -> Self::Output {
+        Self::wrap(pointer_element(array, idx))
+    }
+}
+
+unsafe impl<'frame, 'data> JuliaTypecheck for NamedTuple<'frame, 'data> {
+    unsafe fn julia_typecheck(t: DataType) -> bool {
+        datatype::NamedTuple::julia_typecheck(t)
+    }
+}
+
+impl_valid_layout!(NamedTuple<'frame, 'data>, 'frame, 'data);
+
+// Creates a `NamedTuple` by calling `NamedTuple{names}(values)` and assigns it to `output`. The
+// intermediate values are rooted in a nested frame with three slots.
+pub(crate) unsafe fn new_output<'output, 'fr, 'data, F>(
+    frame: &mut F,
+    output: Output<'output>,
+    names: &[Symbol],
+    values: &[Value<'_, 'data>],
+) -> JlrsResult<NamedTuple<'output, 'data>>
+where
+    F: Frame<'fr>,
+{
+    let exception = |e: Value| JlrsError::Exception(e.type_name().into());
+    let res = frame.frame(3, |frame| {
+        let global = Global::new();
+        let core = Module::core(global);
+        let tuple = core.function("tuple")?;
+
+        let names: Vec<Value> = names.iter().map(|&name| name.into()).collect();
+        let names = tuple.call(&mut *frame, names)?.map_err(exception)?;
+        let ty = core
+            .function("apply_type")?
+            .call2(&mut *frame, core.global("NamedTuple")?, names)?
+            .map_err(exception)?;
+        let values = tuple
+            .call(&mut *frame, values.to_vec())?
+            .map_err(exception)?;
+
+        Ok(jl_call1(ty.ptr(), values.ptr()))
+    })?;
+
+    let exc = jl_exception_occurred();
+    if !exc.is_null() {
+        Err(exception(Value::wrap(exc)))?;
+    }
+
+    Ok(NamedTuple::wrap(
+        frame.assign_output(output, res, Internal).ptr(),
+    ))
+}
@@ -20,10 +20,41 @@
 //! # });
 //! # }
 //! ```
+//!
+//! Rust tuples of up to twelve elements can also be used. A tuple of types that implement
+//! `IntoJulia` can be converted to a Julia `Tuple` with `Value::new`, and a Julia `Tuple` can be
+//! cast to a tuple of types that implement `Cast` as long as their number matches. Unlike the
+//! types in this module these tuples don't need to have an isbits layout. Elements that aren't
+//! stored inline, like strings and arrays, can be cast to a `Value`:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::JULIA;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! julia.frame(2, |global, frame| {
+//!     let val = Value::new(frame, (1i64, String::from("foo")))?;
+//!     assert_eq!(val.cast::<(i64, String)>()?, (1, String::from("foo")));
+//!
+//!     let (_, s) = val.cast::<(i64, Value)>()?;
+//!     assert!(s.is::<String>());
+//!     Ok(())
+//! }).unwrap();
+//! # });
+//! # }
+//! ```
 
 use super::datatype::DataType;
-use crate::traits::JuliaTypecheck;
-use jl_sys::jl_tuple_typename;
+use super::Value;
+use crate::error::{JlrsError, JlrsResult};
+use crate::traits::{Cast, IntoJulia, JuliaTypecheck};
+use jl_sys::{
+    jl_apply_tuple_type_v, jl_get_ptls_states, jl_new_structv, jl_tuple_typename, jl_typeof,
+    jl_value_t,
+};
+use std::ffi::c_void;
+use std::ptr::null_mut;
 
 /// A typecheck that can be used in combination with `DataType::is`. This method returns true if
 /// a value of this type is a tuple.
@@ -262,3 +293,64 @@ impl_tuple!(
     Tuple32, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19,
     T20, T21, T22, T23, T24, T25, T26, T27, T28, T29, T30, T31, T32
 );
+
+macro_rules! impl_std_tuple {
+    ($n:expr, $($types:ident: $idx:tt),+) => {
+        unsafe impl<$($types),+> IntoJulia for ($($types,)+) where $($types: IntoJulia),+ {
+            unsafe fn into_julia(&self) -> *mut jl_value_t {
+                // The type and elements of the tuple are rooted in a GC frame until the tuple
+                // has been created, like `JL_GC_PUSHARGS` does. The frame contains the number of
+                // roots, the previous frame, and the roots.
+                let mut gc_frame: [*mut c_void; $n + 3] = [null_mut(); $n + 3];
+                gc_frame[0] = (($n + 1) << 1) as _;
+                gc_frame[1] = (*jl_get_ptls_states()).pgcstack.cast();
+                (*jl_get_ptls_states()).pgcstack = gc_frame.as_mut_ptr().cast();
+
+                $(gc_frame[$idx + 3] = self.$idx.into_julia().cast();)+
+                let mut types = [$(jl_typeof(gc_frame[$idx + 3].cast())),+];
+                let ty = jl_apply_tuple_type_v(types.as_mut_ptr(), $n);
+                gc_frame[2] = ty.cast();
+                let tuple = jl_new_structv(ty, gc_frame[3..].as_mut_ptr().cast(), $n);
+
+                (*jl_get_ptls_states()).pgcstack = gc_frame[1].cast();
+                tuple
+            }
+        }
+
+        unsafe impl<'frame, 'data, $($types),+> Cast<'frame, 'data> for ($($types,)+)
+        where
+            $($types: Cast<'frame, 'data>),+
+        {
+            type Output = ($($types::Output,)+);
+
+            fn cast(value: Value<'frame, 'data>) -> JlrsResult<Self::Output> {
+                if !value.is::<Tuple>() || value.n_fields() != $n {
+                    Err(JlrsError::WrongType)?
+                }
+
+                Ok(($($types::cast_field(value, $idx)?,)+))
+            }
+
+            unsafe fn cast_unchecked(value: Value<'frame, 'data>) -> Self::Output {
+                ($($types::cast_field_unchecked(value, $idx),)+)
+            }
+        }
+    };
+}
+
+impl_std_tuple!(1, T1: 0);
+impl_std_tuple!(2, T1: 0, T2: 1);
+impl_std_tuple!(3, T1: 0, T2: 1, T3: 2);
+impl_std_tuple!(4, T1: 0, T2: 1, T3: 2, T4: 3);
+impl_std_tuple!(5, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4);
+impl_std_tuple!(6, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5);
+impl_std_tuple!(7, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5, T7: 6);
+impl_std_tuple!(8, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5, T7: 6, T8: 7);
+impl_std_tuple!(9, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5, T7: 6, T8: 7, T9: 8);
+impl_std_tuple!(10, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5, T7: 6, T8: 7, T9: 8, T10: 9);
+impl_std_tuple!(
+    11, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5, T7: 6, T8: 7, T9: 8, T10: 9, T11: 10
+);
+impl_std_tuple!(
+    12, T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5, T7: 6, T8: 7, T9: 8, T10: 9, T11: 10, T12: 11
+);
@@ -1,9 +1,9 @@
 //! Support for values with the `Core.UnionAll` type.
 
 use super::type_var::TypeVar;
-use super::Value;
+use super::{CallResult, Value};
 use crate::error::{JlrsError, JlrsResult};
-use crate::traits::Cast;
+use crate::traits::{Cast, Frame};
 use crate::value::datatype::DataType;
 use crate::{impl_julia_type, impl_julia_typecheck, impl_valid_layout};
 use jl_sys::{jl_unionall_t, jl_unionall_type};
@@ -46,6 +46,24 @@ impl<'frame> UnionAll<'frame> {
     pub fn var(self) -> TypeVar<'frame> {
         unsafe { TypeVar::wrap((&*self.ptr()).var) }
     }
+
+    /// Apply this `UnionAll` to the type parameters `params`, eg `Vector` and `[Float64]` return
+    /// `Vector{Float64}`. This takes one slot on the GC stack. Returns the new type if the
+    /// parameters are valid, the exception if they're not, or an error if no space is left on
+    /// the stack. See [`Value::apply_type`] for more information.
+    ///
+    /// [`Value::apply_type`]: ../struct.Value.html#method.apply_type
+    pub fn apply<'fr, 'value, 'borrow, V, F>(
+        self,
+        frame: &mut F,
+        params: V,
+    ) -> JlrsResult<CallResult<'fr, 'static>>
+    where
+        V: AsMut<[Value<'value, 'borrow>]>,
+        F: Frame<'fr>,
+    {
+        Into::<Value>::into(self).apply_type(frame, params)
+    }
 }
 
 impl<'frame> Into<Value<'frame, 'static>> for UnionAll<'frame> {
@@ -0,0 +1,78 @@
+//! Detect the version of Julia that jlrs has been linked with.
+//!
+//! The bindings that jlrs uses are generated for a specific version of Julia, the layout of
+//! Julia's internal structs can change between minor versions so linking with another version
+//! can cause undefined behavior. When Julia is initialized the version of the runtime is checked,
+//! if it's incompatible with the bindings [`JlrsError::IncompatibleVersion`] is returned and
+//! Julia isn't initialized. The version can be queried with [`Julia::version`].
+//!
+//! [`JlrsError::IncompatibleVersion`]: ../error/enum.JlrsError.html#variant.IncompatibleVersion
+//! [`Julia::version`]: ../struct.Julia.html#method.version
+
+use crate::error::{JlrsError, JlrsResult};
+use jl_sys::{
+    jl_ver_is_release, jl_ver_major, jl_ver_minor, jl_ver_patch, JULIA_VERSION_MAJOR,
+    JULIA_VERSION_MINOR,
+};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The version of Julia jlrs has been linked with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JuliaVersion {
+    /// The major version.
+    pub major: u32,
+    /// The minor version.
+    pub minor: u32,
+    /// The patch version.
+    pub patch: u32,
+    /// `false` if this is a pre-release or development version.
+    pub is_release: bool,
+}
+
+impl JuliaVersion {
+    /// The major and minor version of Julia the bindings have been generated for.
+    pub const SUPPORTED: (u32, u32) = (JULIA_VERSION_MAJOR, JULIA_VERSION_MINOR);
+
+    /// Returns the version of the Julia runtime. This can be called before Julia has been
+    /// initialized.
+    pub fn runtime() -> Self {
+        unsafe {
+            JuliaVersion {
+                major: jl_ver_major() as u32,
+                minor: jl_ver_minor() as u32,
+                patch: jl_ver_patch() as u32,
+                is_release: jl_ver_is_release() != 0,
+            }
+        }
+    }
+
+    /// Returns `true` if this version is compatible with the bindings used by jlrs, i.e. if the
+    /// major and minor version match [`JuliaVersion::SUPPORTED`].
+    ///
+    /// [`JuliaVersion::SUPPORTED`]: struct.JuliaVersion.html#associatedconstant.SUPPORTED
+    pub fn is_supported(self) -> bool {
+        (self.major, self.minor) == Self::SUPPORTED
+    }
+}
+
+impl Display for JuliaVersion {
+    fn fmt(&self, formatter: &mut Formatter) -> FmtResult {
+        write!(formatter, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.is_release {
+            write!(formatter, "-DEV")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Called before Julia is initialized and before `INIT` is set, so `INIT` remains unset if the
+// version isn't supported.
+pub(crate) fn check_version() -> JlrsResult<()> {
+    let version = JuliaVersion::runtime();
+    if !version.is_supported() {
+        Err(JlrsError::IncompatibleVersion(version))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use jlrs::value::union_all::UnionAll;
+
+#[test]
+fn apply_parametric_types() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(6, |global, frame| {
+            let base = Module::base(global);
+            let core = Module::core(global);
+            let float64 = core.global("Float64")?;
+            let string = core.global("String")?;
+            let int64 = core.global("Int64")?;
+
+            let vector = core.global("Array")?.cast::<UnionAll>()?;
+            let one = Value::new(&mut *frame, 1isize)?;
+            let ty = vector.apply(&mut *frame, [float64, one])?.unwrap();
+            assert!(ty.is::<DataType>());
+            assert_eq!(ty.cast::<DataType>()?.name(), "Array");
+
+            let dict = base.global("Dict")?;
+            let ty = dict.apply_type(&mut *frame, [string, int64])?.unwrap();
+            let dt = ty.cast::<DataType>()?;
+            assert_eq!(dt.name(), "Dict");
+            assert_eq!(dt.parameters().len(), 2);
+
+            let res = vector.apply(&mut *frame, [one, one, one])?;
+            assert!(res.is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn instantiate_struct() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(main, "struct InstantiateTest{T}; a::T; b::String; end")?
+                .unwrap();
+
+            let ty = main
+                .global("InstantiateTest")?
+                .apply_type(&mut *frame, [Module::core(global).global("Int64")?])?
+                .unwrap()
+                .cast::<DataType>()?;
+
+            let a = Value::new(&mut *frame, 3i64)?;
+            let b = Value::new(&mut *frame, "b")?;
+            let value = ty.instantiate(&mut *frame, [a, b])?;
+            assert_eq!(value.get_field(&mut *frame, "a")?.cast::<i64>()?, 3);
+            assert_eq!(value.get_field(&mut *frame, "b")?.cast::<String>()?, "b");
+
+            assert!(ty.instantiate(&mut *frame, [a]).is_err());
+            assert!(ty.instantiate(&mut *frame, [b, a]).is_err());
+
+            let abstract_ty = main.global("InstantiateTest")?.cast::<UnionAll>()?;
+            assert!(abstract_ty
+                .base_type()
+                .instantiate(&mut *frame, [a, b])
+                .is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
@@ -0,0 +1,48 @@
+use jlrs::prelude::*;
+
+// Julia can only be initialized once per process, so the builder is tested in its own test
+// binary rather than through `jlrs::util::JULIA`.
+#[test]
+fn builder_sets_options() {
+    unsafe {
+        // An invalid option is rejected before Julia is initialized, so it can be retried.
+        assert!(JuliaBuilder::new(16).optimization_level(4).start().is_err());
+    }
+
+    let mut julia = unsafe {
+        JuliaBuilder::new(16)
+            .n_threads(2)
+            .optimization_level(1)
+            .check_bounds(true)
+            .arg("--compile=yes")
+            .start()
+            .unwrap()
+    };
+
+    julia
+        .frame(5, |global, frame| {
+            let n_threads = Module::base(global)
+                .submodule("Threads")?
+                .function("nthreads")?
+                .call0(frame)?
+                .unwrap()
+                .cast::<isize>()?;
+            assert_eq!(n_threads, 2);
+
+            let opts = Module::base(global)
+                .function("JLOptions")?
+                .call0(frame)?
+                .unwrap();
+            let opt_level = opts.get_field(frame, "opt_level")?.cast::<i8>()?;
+            assert_eq!(opt_level, 1);
+
+            let check_bounds = opts.get_field(frame, "check_bounds")?.cast::<i8>()?;
+            assert_eq!(check_bounds, 1);
+            Ok(())
+        })
+        .unwrap();
+
+    unsafe {
+        assert!(JuliaBuilder::new(16).start().is_err());
+    }
+}
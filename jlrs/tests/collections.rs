@@ -0,0 +1,214 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+#[test]
+fn cast_arrays_to_vec() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let ints = frame.eval_string(main, "[1, 2, 3]")?.unwrap();
+            assert_eq!(ints.cast::<Vec<i64>>()?, [1, 2, 3]);
+            assert!(ints.cast::<Vec<f64>>().is_err());
+            assert!(ints.cast::<Vec<Value>>().is_err());
+
+            let strings = frame.eval_string(main, "[\"a\", \"bc\"]")?.unwrap();
+            assert_eq!(strings.cast::<Vec<String>>()?, ["a", "bc"]);
+            assert_eq!(strings.cast::<Vec<Value>>()?.len(), 2);
+
+            let nested = frame.eval_string(main, "[[1.0], [2.0, 3.0], []]")?.unwrap();
+            let nested = nested.cast::<Vec<Vec<f64>>>()?;
+            assert_eq!(nested, vec![vec![1.0], vec![2.0, 3.0], vec![]]);
+
+            let optional = frame.eval_string(main, "[1, nothing, missing]")?.unwrap();
+            assert_eq!(optional.cast::<Vec<Option<i64>>>()?, [Some(1), None, None]);
+
+            let matrix = frame.eval_string(main, "[1 2; 3 4]")?.unwrap();
+            assert_eq!(matrix.cast::<Vec<i64>>()?, [1, 3, 2, 4]);
+
+            let tuples = frame.eval_string(main, "[(1, 2.0), (3, 4.0)]")?.unwrap();
+            assert_eq!(tuples.cast::<Vec<(i64, f64)>>()?, [(1, 2.0), (3, 4.0)]);
+
+            assert!(Value::new(&mut *frame, 1u8)?.cast::<Vec<u8>>().is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn cast_dict_to_hashmap() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let dict = frame
+                .eval_string(
+                    main,
+                    "d = Dict(\"a\" => 1.0, \"b\" => 2.0); delete!(d, \"b\"); d[\"c\"] = 3.0; d",
+                )?
+                .unwrap();
+
+            let map = dict.cast::<HashMap<String, f64>>()?;
+            assert_eq!(map.len(), 2);
+            assert_eq!(map["a"], 1.0);
+            assert_eq!(map["c"], 3.0);
+            assert!(dict.cast::<HashMap<String, i64>>().is_err());
+
+            let nested = frame
+                .eval_string(main, "Dict(1 => [\"x\", \"y\"])")?
+                .unwrap()
+                .cast::<HashMap<i64, Vec<String>>>()?;
+            assert_eq!(nested[&1], ["x", "y"]);
+
+            let arr = frame.eval_string(main, "[1, 2]")?.unwrap();
+            assert!(arr.cast::<HashMap<i64, i64>>().is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn cast_set_to_hashset() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let set = frame
+                .eval_string(main, "Set([\"x\", \"y\", \"x\"])")?
+                .unwrap()
+                .cast::<HashSet<String>>()?;
+
+            let expected: HashSet<String> = ["x", "y"].iter().map(|s| s.to_string()).collect();
+            assert_eq!(set, expected);
+
+            let dict = frame.eval_string(main, "Dict(1 => 2)")?.unwrap();
+            assert!(dict.cast::<HashSet<i64>>().is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn cast_collections_unchecked() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let strings = frame.eval_string(main, "[\"a\", \"bc\"]")?.unwrap();
+            let strings = unsafe { strings.cast_unchecked::<Vec<String>>() };
+            assert_eq!(strings, ["a", "bc"]);
+
+            let dict = frame
+                .eval_string(main, "d = Dict(1 => 2.0, 3 => 4.0); delete!(d, 1); d")?
+                .unwrap();
+            let map = unsafe { dict.cast_unchecked::<HashMap<i64, f64>>() };
+            assert_eq!(map.len(), 1);
+            assert_eq!(map[&3], 4.0);
+
+            let set = frame.eval_string(main, "Set([1, 2, 1])")?.unwrap();
+            let set = unsafe { set.cast_unchecked::<HashSet<i64>>() };
+            let expected: HashSet<i64> = [1, 2].iter().copied().collect();
+            assert_eq!(set, expected);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn create_vector_from_vec() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+
+            let ints = Value::new(&mut *frame, vec![1i64, 2, 3])?;
+            let ty = frame.eval_string(main, "Vector{Int64}")?.unwrap();
+            assert!(ints.isa(ty));
+            assert_eq!(ints.cast::<Vec<i64>>()?, [1, 2, 3]);
+
+            let strings = Value::new(&mut *frame, &["a", "bc"][..])?;
+            let ty = frame.eval_string(main, "Vector{String}")?.unwrap();
+            assert!(strings.isa(ty));
+            assert_eq!(strings.cast::<Vec<String>>()?, ["a", "bc"]);
+
+            let optional = Value::new(&mut *frame, vec![Some(1.0f64), None])?;
+            let ty = frame
+                .eval_string(main, "Vector{Union{Nothing, Float64}}")?
+                .unwrap();
+            assert!(optional.isa(ty));
+            assert_eq!(optional.cast::<Vec<Option<f64>>>()?, [Some(1.0), None]);
+
+            let nested = Value::new(&mut *frame, vec![vec![1u8], vec![]])?;
+            let ty = frame.eval_string(main, "Vector{Vector{UInt8}}")?.unwrap();
+            assert!(nested.isa(ty));
+            assert_eq!(nested.cast::<Vec<Vec<u8>>>()?, vec![vec![1], vec![]]);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn create_dict_from_map() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+
+            let mut map = HashMap::new();
+            map.insert(String::from("a"), 1.0f64);
+            map.insert(String::from("b"), 2.0);
+            let dict = Value::new_dict(&mut *frame, &map)?.unwrap();
+            let ty = frame.eval_string(main, "Dict{String, Float64}")?.unwrap();
+            assert!(dict.isa(ty));
+            assert_eq!(dict.cast::<HashMap<String, f64>>()?, map);
+
+            let mut map = BTreeMap::new();
+            map.insert(1i32, vec!["x"]);
+            let dict = Value::new_dict(&mut *frame, &map)?.unwrap();
+            let ty = frame
+                .eval_string(main, "Dict{Int32, Vector{String}}")?
+                .unwrap();
+            assert!(dict.isa(ty));
+            assert_eq!(dict.cast::<HashMap<i32, Vec<String>>>()?[&1], ["x"]);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn create_set_from_set() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+
+            let set: HashSet<u16> = [1, 2, 3].iter().copied().collect();
+            let value = Value::new_set(&mut *frame, &set)?.unwrap();
+            let ty = frame.eval_string(main, "Set{UInt16}")?.unwrap();
+            assert!(value.isa(ty));
+            assert_eq!(value.cast::<HashSet<u16>>()?, set);
+
+            let set: BTreeSet<&str> = ["x", "y"].iter().copied().collect();
+            let value = Value::new_set(&mut *frame, &set)?.unwrap();
+            let ty = frame.eval_string(main, "Set{String}")?.unwrap();
+            assert!(value.isa(ty));
+            assert_eq!(value.cast::<HashSet<String>>()?.len(), 2);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
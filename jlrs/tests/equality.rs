@@ -0,0 +1,81 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn egal_and_isa() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(4, |global, frame| {
+            let a = Value::new(&mut *frame, 1i64)?;
+            let b = Value::new(&mut *frame, 1i64)?;
+            let c = Value::new(&mut *frame, 1i32)?;
+            let s = Value::new(&mut *frame, "s")?;
+
+            assert!(a.egal(b));
+            assert_eq!(a, b);
+            assert_eq!(a.object_id(), b.object_id());
+            assert_ne!(a, c);
+
+            let core = Module::core(global);
+            assert!(a.isa(core.global("Int64")?));
+            assert!(a.isa(core.global("Integer")?));
+            assert!(!a.isa(core.global("String")?));
+            assert!(s.isa(core.global("AbstractString")?));
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn isequal_and_hash() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(8, |global, frame| {
+            let a = Value::new(&mut *frame, 1i64)?;
+            let b = Value::new(&mut *frame, 1.0f64)?;
+            let c = Value::new(&mut *frame, 2i64)?;
+
+            assert!(!a.egal(b));
+            assert!(a.isequal(&mut *frame, b)?);
+            assert!(!a.isequal(&mut *frame, c)?);
+            assert_eq!(a.hash(&mut *frame)?, b.hash(&mut *frame)?);
+
+            let func = Module::base(global).function("hash")?;
+            assert!(func.hash(&mut *frame).is_ok());
+            assert_eq!(frame.size(), 8);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn values_as_keys() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let a = Value::new(&mut *frame, 3u8)?;
+            let b = Value::new(&mut *frame, 3u8)?;
+            let v1 = frame.eval_string(main, "[1, 2]")?.unwrap();
+            let v2 = frame.eval_string(main, "[1, 2]")?.unwrap();
+
+            let mut map = HashMap::new();
+            map.insert(a, "a");
+            map.insert(v1, "v1");
+            assert_eq!(map.get(&b), Some(&"a"));
+            assert_eq!(map.get(&v1), Some(&"v1"));
+            assert!(map.get(&v2).is_none());
+
+            let set: HashSet<Value> = [a, b, v1, v2].iter().copied().collect();
+            assert_eq!(set.len(), 3);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
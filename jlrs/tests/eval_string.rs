@@ -0,0 +1,108 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn eval_string_returns_result() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(3, |global, frame| {
+            let res = frame.eval_string(Module::main(global), "x = 1 + 2\nx * 2")?;
+            assert_eq!(res.unwrap().cast::<i64>()?, 6);
+            Ok(())
+        })
+        .unwrap()
+    });
+}
+
+#[test]
+fn eval_string_returns_exception() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let res = frame.eval_string(Module::main(global), "throw(ArgumentError(\"x\"))")?;
+            let exception = res.unwrap_err();
+            assert_eq!(exception.type_name(), "LoadError");
+            assert_eq!(
+                exception.get_field(frame, "error")?.type_name(),
+                "ArgumentError"
+            );
+            Ok(())
+        })
+        .unwrap()
+    });
+}
+
+#[test]
+fn include_string_defines_function() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let res = jlrs
+            .include_string(
+                |global| Ok(Module::main(global)),
+                "jlrs_eval_string_add_one(x) = x + 1\njlrs_eval_string_add_one(1)",
+                "add_one.jl",
+                |_, _, result| result.unwrap().cast::<i64>(),
+            )
+            .unwrap();
+        assert_eq!(res, 2);
+
+        jlrs.frame(2, |global, frame| {
+            let one = Value::new(frame, 1i64)?;
+            let res = Module::main(global)
+                .function("jlrs_eval_string_add_one")?
+                .call1(frame, one)?
+                .unwrap()
+                .cast::<i64>()?;
+            assert_eq!(res, 2);
+            Ok(())
+        })
+        .unwrap()
+    });
+}
+
+#[test]
+fn eval_string_in_module() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let module = jlrs
+            .eval_string(
+                |global| Ok(Module::base(global)),
+                "nameof(@__MODULE__)",
+                |_, _, result| result.unwrap().cast::<Symbol>().map(Into::<String>::into),
+            )
+            .unwrap();
+        assert_eq!(module, "Base");
+
+        let res = jlrs.eval_string(
+            |global| Module::main(global).submodule("NoSuchModule"),
+            "1",
+            |_, _, _| Ok(()),
+        );
+        assert!(res.is_err());
+    });
+}
+
+#[test]
+fn include_string_returns_exception() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let file = jlrs
+            .include_string(
+                |global| Ok(Module::main(global)),
+                "1 +* 2",
+                "parse_error.jl",
+                |_, frame, result| {
+                    let exception = result.unwrap_err();
+                    assert_eq!(exception.type_name(), "LoadError");
+                    exception.get_field(frame, "file")?.cast::<String>()
+                },
+            )
+            .unwrap();
+        assert_eq!(file, "parse_error.jl");
+    });
+}
@@ -0,0 +1,95 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use jlrs::value::expr::Expr;
+
+#[test]
+fn build_and_eval_expr() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let plus = Expr::global_ref(&mut *frame, Module::base(global), "+")?;
+            let one = Value::new(&mut *frame, 1i64)?;
+            let two = Value::new(&mut *frame, 2i64)?;
+
+            let expr = Expr::new(&mut *frame, "call", [plus, one, two])?.unwrap();
+            assert_eq!(Into::<String>::into(expr.cast::<Expr>()?.head()), "call");
+
+            let res = main.eval(&mut *frame, expr)?.unwrap();
+            assert_eq!(res.cast::<i64>()?, 3);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn define_function_with_expr() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let name: Value = Symbol::new(global, "expr_kernel").into();
+            let x: Value = Symbol::new(global, "x").into();
+            let mul: Value = Symbol::new(global, "*").into();
+            let three = Value::new(&mut *frame, 3i64)?;
+
+            let sig = Expr::new(&mut *frame, "call", [name, x])?.unwrap();
+            let body = Expr::new(&mut *frame, "call", [mul, three, x])?.unwrap();
+            let line = Expr::line_number_node(&mut *frame, 1, Some("kernel.jl"))?.unwrap();
+            let block = Expr::new(&mut *frame, "block", [line, body])?.unwrap();
+            let def = Expr::new(&mut *frame, "function", [sig, block])?.unwrap();
+            main.eval(&mut *frame, def)?.unwrap();
+
+            let arg = Value::new(&mut *frame, 4i64)?;
+            let res = main.function("expr_kernel")?.call1(&mut *frame, arg)?;
+            assert_eq!(res.unwrap().cast::<i64>()?, 12);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn quote_values() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let sym: Value = Symbol::new(global, "not_defined_anywhere").into();
+            let quoted = Expr::quote_node(&mut *frame, sym)?.unwrap();
+            assert_eq!(quoted.type_name(), "QuoteNode");
+
+            let res = main.eval(&mut *frame, quoted)?.unwrap();
+            assert!(res.is::<Symbol>());
+            assert!(main.eval(&mut *frame, sym)?.is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn parse_code() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            let expr = Expr::parse(&mut *frame, "2 * 21")?.unwrap();
+            let res = main.eval(&mut *frame, expr)?.unwrap();
+            assert_eq!(res.cast::<i64>()?, 42);
+
+            let expr = Expr::parse(&mut *frame, "1")?.unwrap();
+            assert!(!expr.is::<Expr>());
+
+            let res = Expr::parse(&mut *frame, "1 2")?;
+            assert_eq!(res.unwrap_err().type_name(), "ParseError");
+            Ok(())
+        })
+        .unwrap();
+    });
+}
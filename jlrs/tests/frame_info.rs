@@ -0,0 +1,88 @@
+use jlrs::frame::{FrameInfo, FrameKind};
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn static_frame_info() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(4, |_, frame| {
+            let info = frame.frame_info(false);
+            assert_eq!(info.kind, FrameKind::Static);
+            assert_eq!(info.capacity, Some(4));
+            assert_eq!(info.len, 0);
+            assert_eq!(info.outputs, 0);
+            assert!(info.root_types.is_none());
+
+            Value::new(&mut *frame, 1usize)?;
+            Value::new(&mut *frame, 2.0f32)?;
+            frame.output()?;
+
+            let info = frame.frame_info(true);
+            assert_eq!(info.len, 3);
+            assert_eq!(info.outputs, 1);
+            assert_eq!(
+                info.root_types.unwrap(),
+                vec![Some("UInt64".into()), Some("Float32".into()), None]
+            );
+
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn dynamic_frame_info() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|_, frame| {
+            for i in 0..64u8 {
+                Value::new(&mut *frame, i)?;
+            }
+
+            let info = frame.frame_info(true);
+            assert_eq!(info.kind, FrameKind::Dynamic);
+            assert_eq!(info.capacity, None);
+            assert_eq!(info.len, 64);
+            assert_eq!(info.outputs, 0);
+
+            let types = info.root_types.unwrap();
+            assert_eq!(types.len(), 64);
+            assert!(types.iter().all(|ty| ty.as_ref().unwrap() == "UInt8"));
+
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn nested_frame_chain() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|_, frame| {
+            Value::new(&mut *frame, 1i32)?;
+
+            frame.frame(2, |frame| {
+                Value::new(&mut *frame, 2i64)?;
+
+                let chain: Vec<FrameInfo> = frame.frame_chain(true);
+                assert_eq!(chain.len(), 2);
+                assert_eq!(chain[0].kind, FrameKind::Static);
+                assert_eq!(chain[0].root_types, Some(vec![Some("Int64".into())]));
+                assert_eq!(chain[1].kind, FrameKind::Dynamic);
+                assert_eq!(chain[1].root_types, Some(vec![Some("Int32".into())]));
+
+                Ok(())
+            })?;
+
+            assert_eq!(frame.frame_chain(false).len(), 1);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
@@ -0,0 +1,48 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn collect_updates_stats() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let before = jlrs.gc_stats().unwrap();
+        jlrs.gc_collect(GcCollection::Full);
+        jlrs.gc_collect(GcCollection::Incremental);
+        let after = jlrs.gc_stats().unwrap();
+
+        assert!(after.collections >= before.collections + 2);
+        assert!(after.full_collections > before.full_collections);
+        assert!(after.allocated_bytes >= before.allocated_bytes);
+    });
+}
+
+#[test]
+fn disable_gc_with_guard() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        assert!(jlrs.gc_is_enabled());
+        {
+            let _guard = jlrs.disable_gc();
+            assert!(!jlrs.gc_is_enabled());
+        }
+        assert!(jlrs.gc_is_enabled());
+    });
+}
+
+#[test]
+fn gc_from_frame() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(1, |_, frame| {
+            let v = Value::new(frame, 1u64)?;
+            frame.gc_collect(GcCollection::Full);
+            assert_eq!(v.cast::<u64>()?, 1);
+            assert!(frame.gc_stats().is_ok());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
@@ -0,0 +1,118 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn dynamic_frame_grows_stack() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.set_stack_size(8);
+
+        let sum = jlrs
+            .dynamic_frame(|_global, frame| {
+                let mut values = Vec::new();
+                for i in 0..1000u64 {
+                    values.push(Value::new(frame, i)?);
+                }
+
+                let mut sum = 0;
+                for value in values {
+                    sum += value.cast::<u64>()?;
+                }
+
+                Ok(sum)
+            })
+            .unwrap();
+
+        assert_eq!(sum, 499500);
+        assert!(jlrs.stack_size() > 1000);
+    });
+}
+
+#[test]
+fn static_frame_larger_than_stack() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.set_stack_size(8);
+
+        jlrs.frame(100, |_global, frame| {
+            for i in 0..100u64 {
+                Value::new(frame, i)?;
+            }
+
+            assert!(Value::new(frame, 100u64).is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn nested_frames_grow_stack() {
+    fn nest<'frame, F: Frame<'frame>>(frame: &mut F, depth: usize) -> JlrsResult<usize> {
+        if depth == 0 {
+            return Ok(0);
+        }
+
+        frame.dynamic_frame(|frame| {
+            let v = Value::new(frame, depth)?;
+            let below = frame.frame(2, |frame| {
+                Value::new(frame, depth)?;
+                nest(frame, depth - 1)
+            })?;
+            Ok(below + v.cast::<usize>()?)
+        })
+    }
+
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.set_stack_size(8);
+
+        let sum = jlrs
+            .dynamic_frame(|_global, frame| nest(frame, 100))
+            .unwrap();
+        assert_eq!(sum, 5050);
+    });
+}
+
+#[test]
+fn values_span_segments() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.set_stack_size(8);
+
+        jlrs.dynamic_frame(|global, frame| {
+            Value::new(frame, 0u8)?;
+            let values = Values::new(frame, [1u64, 2, 3, 4, 5, 6, 7, 8, 9, 10])?;
+            let add = Module::base(global).function("+")?;
+            let sum = add.call_values(frame, values)?.unwrap();
+            assert_eq!(sum.cast::<u64>()?, 55);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn max_stack_size() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.set_stack_size(16);
+        jlrs.set_max_stack_size(Some(64));
+        assert_eq!(jlrs.max_stack_size(), Some(64));
+
+        assert!(jlrs.frame(100, |_global, _frame| Ok(())).is_err());
+        let res = jlrs.dynamic_frame(|_global, frame| {
+            for i in 0..100u64 {
+                Value::new(frame, i)?;
+            }
+            Ok(())
+        });
+        assert!(res.is_err());
+        assert!(jlrs.stack_size() <= 64);
+
+        jlrs.frame(40, |_global, _frame| Ok(())).unwrap();
+
+        jlrs.set_max_stack_size(None);
+        jlrs.frame(100, |_global, _frame| Ok(())).unwrap();
+    });
+}
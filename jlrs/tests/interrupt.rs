@@ -0,0 +1,139 @@
+use jlrs::interrupt::Interrupt;
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn call_completes_before_timeout() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(6, |global, frame| {
+            let a = Value::new(frame, 1u64)?;
+            let b = Value::new(frame, 2u64)?;
+            let res = Module::base(global)
+                .function("+")?
+                .call_timeout(frame, [a, b], Duration::from_secs(10))?
+                .unwrap()
+                .cast::<u64>()?;
+            assert_eq!(res, 3);
+            Ok(())
+        })
+        .unwrap()
+    });
+}
+
+#[test]
+fn call_times_out() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(5, |global, frame| {
+            let secs = Value::new(frame, 30.0f64)?;
+            let res = Module::base(global).function("sleep")?.call_timeout(
+                frame,
+                [secs],
+                Duration::from_millis(100),
+            );
+
+            match res {
+                Err(e) => match *e {
+                    JlrsError::Interrupted => (),
+                    _ => panic!("Unexpected error"),
+                },
+                Ok(_) => panic!("Call should have been interrupted"),
+            }
+            Ok(())
+        })
+        .unwrap()
+    });
+}
+
+#[test]
+fn interrupt_from_other_thread() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        let interrupt = Interrupt::new();
+        let handle = interrupt.clone();
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            handle.interrupt();
+        });
+
+        jlrs.frame(5, |global, frame| {
+            let secs = Value::new(frame, 30.0f64)?;
+            let res = Module::base(global).function("sleep")?.call_interruptible(
+                frame,
+                [secs],
+                &interrupt,
+                None,
+            );
+            assert!(res.is_err());
+            Ok(())
+        })
+        .unwrap();
+
+        t.join().unwrap();
+        assert!(interrupt.is_interrupted());
+    });
+}
+
+#[test]
+fn busy_loop_is_not_interrupted() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(12, |global, frame| {
+            // This loop never yields, so the timeout can only be handled after it has returned.
+            let main = Module::main(global);
+            let busy_wait = frame
+                .eval_string(
+                    main,
+                    "function jlrs_busy_wait(secs)
+                        start = time()
+                        while time() - start < secs end
+                        secs
+                    end",
+                )?
+                .unwrap();
+
+            let secs = Value::new(frame, 0.5f64)?;
+            let start = Instant::now();
+            let res = busy_wait
+                .call_timeout(frame, [secs], Duration::from_millis(10))?
+                .unwrap()
+                .cast::<f64>()?;
+            assert_eq!(res, 0.5);
+            assert!(start.elapsed() >= Duration::from_millis(500));
+
+            // The same loop can be interrupted if it yields.
+            let yielding_wait = frame
+                .eval_string(
+                    main,
+                    "function jlrs_yielding_wait(secs)
+                        start = time()
+                        while time() - start < secs
+                            yield()
+                        end
+                        secs
+                    end",
+                )?
+                .unwrap();
+
+            let secs = Value::new(frame, 30.0f64)?;
+            let res = yielding_wait.call_timeout(frame, [secs], Duration::from_millis(100));
+            match res {
+                Err(e) => match *e {
+                    JlrsError::Interrupted => (),
+                    _ => panic!("Unexpected error"),
+                },
+                Ok(_) => panic!("Call should have been interrupted"),
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    });
+}
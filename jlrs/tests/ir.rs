@@ -0,0 +1,156 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use jlrs::value::code_info::CodeInfo;
+use jlrs::value::expr::Expr;
+use jlrs::value::ir::{GotoNode, LineNumberNode, PhiNode, QuoteNode, SSAValue};
+
+#[test]
+fn lowered_code_info() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(
+                    main,
+                    "function ir_sum(n)\n    s = 0\n    for i in 1:n\n        s += i\n    end\n    s\nend",
+                )?
+                .unwrap();
+
+            let ci = frame
+                .eval_string(main, "code_lowered(ir_sum, (Int,))[1]")?
+                .unwrap()
+                .cast::<CodeInfo>()?;
+            assert!(!ci.inferred());
+            assert!(ci.parent().is_some());
+
+            let names: Vec<String> = unsafe {
+                ci.slot_names()
+                    .unwrap()
+                    .value_data(frame)?
+                    .as_slice()
+                    .iter()
+                    .map(|name| name.cast::<Symbol>().unwrap().into())
+                    .collect()
+            };
+            assert_eq!(&names[..2], ["#self#", "n"]);
+
+            let (file, line) = ci.line_info(0).unwrap();
+            assert_eq!(Into::<String>::into(file), "string");
+            assert!(line >= 1 && line <= 7);
+
+            let code = unsafe { ci.code().unwrap().value_data(frame)?.as_slice().to_vec() };
+            assert!(code.iter().any(|stmt| stmt.is::<GotoNode>()));
+            let goto = code
+                .iter()
+                .find(|stmt| stmt.is::<GotoNode>())
+                .unwrap()
+                .cast::<GotoNode>()?;
+            assert!(goto.label() > 0 && goto.label() as usize <= code.len());
+
+            let ssa = code
+                .iter()
+                .filter_map(|stmt| stmt.cast::<Expr>().ok())
+                .flat_map(|expr| unsafe {
+                    expr.args()
+                        .value_data(frame)
+                        .unwrap()
+                        .as_slice()
+                        .to_vec()
+                })
+                .find(|arg| arg.is::<SSAValue>())
+                .unwrap();
+            assert!(ssa.cast::<SSAValue>()?.id() >= 1);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn typed_code_info() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(main, "ir_pick(x) = x > 0 ? x : -x")?
+                .unwrap();
+
+            let ci = frame
+                .eval_string(main, "code_typed(ir_pick, (Int,))[1].first")?
+                .unwrap()
+                .cast::<CodeInfo>()?;
+            assert!(ci.inferred());
+            assert!(ci.return_type().egal(Module::core(global).global("Int64")?));
+
+            let code = unsafe { ci.code().unwrap().value_data(frame)?.as_slice().to_vec() };
+            if let Some(phi) = code.iter().find(|stmt| stmt.is::<PhiNode>()) {
+                let phi = phi.cast::<PhiNode>()?;
+                assert_eq!(
+                    phi.edges().dimensions().size(),
+                    phi.values().dimensions().size()
+                );
+            }
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn cast_nodes() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let sym: Value = Symbol::new(global, "quoted").into();
+            let quoted = Expr::quote_node(&mut *frame, sym)?
+                .unwrap()
+                .cast::<QuoteNode>()?;
+            assert!(quoted.value().egal(sym));
+
+            let line = Expr::line_number_node(&mut *frame, 12, Some("nodes.jl"))?
+                .unwrap()
+                .cast::<LineNumberNode>()?;
+            assert_eq!(line.line(), 12);
+            assert_eq!(
+                Into::<String>::into(line.file().cast::<Symbol>()?),
+                "nodes.jl"
+            );
+
+            assert!(sym.cast::<QuoteNode>().is_err());
+            assert!(sym.cast::<CodeInfo>().is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn uninitialized_code_info() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let ci = frame
+                .eval_string(
+                    Module::main(global),
+                    "ccall(:jl_new_code_info_uninit, Ref{Core.CodeInfo}, ())",
+                )?
+                .unwrap()
+                .cast::<CodeInfo>()?;
+
+            assert!(ci.code().is_none());
+            assert!(ci.code_locations().is_none());
+            assert!(ci.line_table().is_none());
+            assert!(ci.line_info(0).is_none());
+            assert!(ci.parent().is_none());
+            assert_eq!(ci.min_world(), 1);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
@@ -0,0 +1,75 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn call_with_keyword_pairs() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let round = Module::base(global).function("round")?;
+            let x = Value::new(&mut *frame, 1.23456f64)?;
+            let digits = Value::new(&mut *frame, 2isize)?;
+            let kws = [(Symbol::new(global, "digits"), digits)];
+
+            let res = round.call_keywords(&mut *frame, [x], &kws)?.unwrap();
+            assert_eq!(res.cast::<f64>()?, 1.23);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn call_with_keyword_struct() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(main, "struct KeywordsTest; digits::Int; base::Int; end")?
+                .unwrap();
+            let opts = frame.eval_string(main, "KeywordsTest(3, 10)")?.unwrap();
+
+            let round = Module::base(global).function("round")?;
+            let x = Value::new(&mut *frame, 1.23456f64)?;
+            let res = round.call_keywords(&mut *frame, [x], opts)?.unwrap();
+            assert_eq!(res.cast::<f64>()?, 1.235);
+
+            let nt = frame.eval_string(main, "(digits = 1,)")?.unwrap();
+            let res = round.call_keywords(&mut *frame, [x], nt)?.unwrap();
+            assert_eq!(res.cast::<f64>()?, 1.2);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn call_with_keywords_and_output() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(4, |global, frame| {
+            let output = frame.output()?;
+            let round = Module::base(global).function("round")?;
+            let x = Value::new(&mut *frame, 1.23456f64)?;
+            let digits = Value::new(&mut *frame, 3isize)?;
+
+            let kws = vec![(Symbol::new(global, "digits"), digits)];
+            let res = round
+                .with_output(output)
+                .call_keywords(&mut *frame, [x], kws)?
+                .unwrap();
+            assert_eq!(res.cast::<f64>()?, 1.235);
+
+            let kws = vec![(Symbol::new(global, "unknown"), digits)];
+            let res = round.call_keywords(&mut *frame, [x], kws)?;
+            assert_eq!(res.unwrap_err().type_name(), "MethodError");
+            assert_eq!(frame.size(), 4);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "log")]
+
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+
+struct TestLogger(Mutex<Vec<(Level, String, String)>>);
+
+impl Log for TestLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push((
+            record.level(),
+            record.target().into(),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: TestLogger = TestLogger(Mutex::new(Vec::new()));
+
+#[test]
+fn forwards_julia_logs() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.install_logger(LevelFilter::Info).unwrap();
+        let logged = jlrs
+            .eval_string(
+                |global| Ok(Module::main(global)),
+                "@debug \"filtered\"; @info \"info\" x = 1; @warn \"warn\"",
+                |_, _, result| Ok(result.is_ok()),
+            )
+            .unwrap();
+        assert!(logged);
+    });
+
+    let records = LOGGER.0.lock().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0], (Level::Info, "Main".into(), "info".into()));
+    assert_eq!(records[1].0, Level::Warn);
+}
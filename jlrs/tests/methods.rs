@@ -0,0 +1,93 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn list_methods() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(
+                    main,
+                    "methods_test(x::Int) = x; methods_test(x::Float64, y) = x; methods_test",
+                )?
+                .unwrap();
+
+            let func = main.function("methods_test")?;
+            let methods = func.methods(&mut *frame)?;
+            assert_eq!(methods.size_hint(), (2, Some(2)));
+
+            let mut n_args: Vec<i32> = methods
+                .map(|method| {
+                    assert_eq!(Into::<String>::into(method.name()), "methods_test");
+                    method.n_args()
+                })
+                .collect();
+            n_args.sort();
+            assert_eq!(n_args, [2, 3]);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn find_method() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(1, |global, frame| {
+            let core = Module::core(global);
+            let int64 = core.global("Int64")?;
+            let string = core.global("String")?;
+            let func = Module::base(global).function("+")?;
+
+            let method = func.which(&mut *frame, [int64, int64])?.unwrap();
+            assert_eq!(Into::<String>::into(method.name()), "+");
+            assert_eq!(method.n_args(), 3);
+
+            assert!(func.which(&mut *frame, [string, string])?.is_none());
+            assert_eq!(frame.size(), 0);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn ambiguous_method() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(
+                    main,
+                    "ambiguous_test(x::Int, y) = 1; ambiguous_test(x, y::Int) = 2; ambiguous_test",
+                )?
+                .unwrap();
+
+            let core = Module::core(global);
+            let int64 = core.global("Int64")?;
+            let float64 = core.global("Float64")?;
+            let func = main.function("ambiguous_test")?;
+
+            let method = func.which(&mut *frame, [int64, float64])?.unwrap();
+            assert_eq!(method.n_args(), 3);
+            assert!(func.which(&mut *frame, [float64, float64])?.is_none());
+
+            match func.which(&mut *frame, [int64, int64]) {
+                Err(e) => match *e {
+                    JlrsError::AmbiguousMethod => (),
+                    _ => panic!("Unexpected error"),
+                },
+                Ok(_) => panic!("The method should be ambiguous"),
+            }
+            Ok(())
+        })
+        .unwrap();
+    });
+}
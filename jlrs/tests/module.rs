@@ -280,3 +280,55 @@ fn set_const_twice() {
         assert!(err.is_err());
     })
 }
+
+#[test]
+fn require_module() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(0, |global, _| {
+            let module = Module::require(global, "LinearAlgebra")?;
+            let name: String = module.name().into();
+            assert_eq!(name, "LinearAlgebra");
+            assert!(module.function("norm").is_ok());
+            Ok(())
+        })
+        .unwrap()
+    });
+}
+
+#[test]
+fn require_nonexistent_package() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(0, |global, _| {
+            match Module::require(global, "JlrsNonexistentPackage") {
+                Err(e) => match *e {
+                    JlrsError::LoadError(_, _) => (),
+                    _ => panic!("Unexpected error"),
+                },
+                Ok(_) => panic!("Package should not exist"),
+            }
+            Ok(())
+        })
+        .unwrap()
+    });
+}
+
+#[test]
+fn using_module() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.using("Base.Iterators").unwrap();
+        assert!(jlrs.using("Base.Not A Module").is_err());
+        assert!(jlrs.activate_project("/jlrs/nonexistent/project").is_err());
+
+        jlrs.frame(0, |global, _| {
+            assert!(Module::main(global).function("flatten").is_ok());
+            Ok(())
+        })
+        .unwrap()
+    });
+}
@@ -0,0 +1,79 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use jlrs::value::named_tuple::NamedTuple;
+
+#[test]
+fn create_named_tuple_from_pairs() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(6, |_, frame| {
+            let a = Value::new(&mut *frame, 1i64)?;
+            let b = Value::new(&mut *frame, "b")?;
+            let nt = NamedTuple::new(&mut *frame, &[("a", a), ("b", b)])?;
+
+            assert_eq!(nt.len(), 2);
+            assert!(!nt.is_empty());
+            assert!(nt.contains("a"));
+            assert!(!nt.contains("c"));
+            assert_eq!(nt.as_value().type_name(), "NamedTuple");
+            assert_eq!(nt.get(&mut *frame, "a")?.cast::<i64>()?, 1);
+            assert_eq!(nt.get(&mut *frame, "b")?.cast::<String>()?, "b");
+            assert!(nt.get(&mut *frame, "c").is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn iterate_named_tuple() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let nt = frame
+                .eval_string(Module::main(global), "(x = 1, y = 2.0)")?
+                .unwrap()
+                .cast::<NamedTuple>()?;
+
+            let names: Vec<String> = nt.names().iter().map(|&name| name.into()).collect();
+            assert_eq!(names, ["x", "y"]);
+
+            let mut iter = nt.iter(&mut *frame);
+            let (name, value) = iter.next().unwrap()?;
+            assert_eq!(Into::<String>::into(name), "x");
+            assert_eq!(value.cast::<i64>()?, 1);
+            let (name, value) = iter.next().unwrap()?;
+            assert_eq!(Into::<String>::into(name), "y");
+            assert_eq!(value.cast::<f64>()?, 2.0);
+            assert!(iter.next().is_none());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn create_named_tuple_from_struct() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(main, "struct NamedTupleTest; a::Int; b::Float32; end")?
+                .unwrap();
+            let s = frame.eval_string(main, "NamedTupleTest(3, 4.0)")?.unwrap();
+
+            let nt = NamedTuple::from_struct(&mut *frame, s)?;
+            assert_eq!(nt.get(&mut *frame, "a")?.cast::<i64>()?, 3);
+            assert_eq!(nt.get(&mut *frame, "b")?.cast::<f32>()?, 4.0);
+
+            assert!(s.cast::<NamedTuple>().is_err());
+            assert!(nt.as_value().cast::<NamedTuple>().is_ok());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
@@ -0,0 +1,70 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use jlrs::value::missing::Missing;
+
+#[test]
+fn cast_nothing_and_missing_to_option() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(2, |global, frame| {
+            let nothing = Value::nothing(&mut *frame);
+            assert_eq!(nothing.cast::<Option<i64>>()?, None);
+
+            let missing = Missing::value(global);
+            assert!(missing.is_missing());
+            assert!(missing.is::<Missing>());
+            assert_eq!(missing.cast::<Missing>()?, Missing);
+            assert_eq!(missing.cast::<Option<f64>>()?, None);
+
+            let x = Value::new(&mut *frame, 3i64)?;
+            assert_eq!(x.cast::<Option<i64>>()?, Some(3));
+            assert!(x.cast::<Option<f32>>().is_err());
+            assert!(x.cast::<Missing>().is_err());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn option_into_julia() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(3, |_, frame| {
+            let some = Value::new(&mut *frame, Some(2u8))?;
+            assert_eq!(some.cast::<u8>()?, 2);
+
+            let none = Value::new(&mut *frame, None::<u8>)?;
+            assert!(none.is_nothing());
+
+            let missing = Value::new(&mut *frame, Missing)?;
+            assert!(missing.is_missing());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn cast_optional_fields() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let tuple = frame
+                .eval_string(Module::main(global), "(1, nothing, missing, \"s\")")?
+                .unwrap();
+
+            let (a, b, c, d) =
+                tuple.cast::<(Option<i64>, Option<i64>, Option<String>, Option<String>)>()?;
+            assert_eq!(a, Some(1));
+            assert_eq!(b, None);
+            assert_eq!(c, None);
+            assert_eq!(d, Some("s".into()));
+            Ok(())
+        })
+        .unwrap();
+    });
+}
@@ -0,0 +1,75 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+fn n_rooted(julia: &mut Julia) -> i64 {
+    julia
+        .frame(3, |global, frame| {
+            frame
+                .eval_string(Module::main(global), "count(!isnothing, Jlrs.persistent)")?
+                .unwrap()
+                .cast::<i64>()
+        })
+        .unwrap()
+}
+
+#[test]
+fn persistent_outlives_frame() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        let n = n_rooted(&mut jlrs);
+
+        let persistent = jlrs
+            .frame(1, |_, frame| {
+                let v = Value::new(frame, "persistent string")?;
+                Persistent::new(frame, v)
+            })
+            .unwrap();
+        assert_eq!(n_rooted(&mut jlrs), n + 1);
+
+        jlrs.gc_collect(GcCollection::Full);
+
+        let copy = persistent.clone();
+        jlrs.frame(0, |_, frame| {
+            assert_eq!(copy.cast::<String, _>(frame)?, "persistent string");
+            Ok(())
+        })
+        .unwrap();
+
+        drop(persistent);
+        drop(copy);
+        jlrs.frame(0, |_, frame| Persistent::release_dropped(frame))
+            .unwrap();
+        assert_eq!(n_rooted(&mut jlrs), n);
+    });
+}
+
+#[test]
+fn equal_values_are_rooted_separately() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let (first, second) = jlrs
+            .frame(2, |_, frame| {
+                let first = Value::new(frame, "equal string")?;
+                let second = Value::new(frame, "equal string")?;
+                // The strings are egal, but they are different objects.
+                assert!(first.egal(second));
+                Ok((
+                    Persistent::new(frame, first)?,
+                    Persistent::new(frame, second)?,
+                ))
+            })
+            .unwrap();
+
+        drop(first);
+        jlrs.frame(0, |_, frame| Persistent::release_dropped(frame))
+            .unwrap();
+        jlrs.gc_collect(GcCollection::Full);
+
+        jlrs.frame(0, |_, frame| {
+            assert_eq!(second.cast::<String, _>(frame)?, "equal string");
+            Ok(())
+        })
+        .unwrap();
+    });
+}
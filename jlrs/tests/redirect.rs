@@ -0,0 +1,80 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn capture_output() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let (_, output) = jlrs
+            .capture_output(|julia| {
+                julia.eval_string(
+                    |global| Ok(Module::main(global)),
+                    "println(\"stdout\"); println(stderr, \"stderr\")",
+                    |_, _, result| Ok(result.is_ok()),
+                )
+            })
+            .unwrap();
+
+        assert!(output.contains("stdout\n"));
+        assert!(output.contains("stderr\n"));
+    });
+}
+
+#[test]
+fn redirect_stdout_to_writer() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        let buffer = Buffer::default();
+
+        jlrs.redirect_stdout(buffer.clone()).unwrap();
+        print_string(&mut jlrs, "outer");
+        let (_, inner) = jlrs
+            .capture_output(|julia| {
+                print_string(julia, "inner");
+                Ok(())
+            })
+            .unwrap();
+        print_string(&mut jlrs, "outer");
+        jlrs.restore_stdout().unwrap();
+
+        assert_eq!(inner, "inner");
+        assert_eq!(&*buffer.0.lock().unwrap(), b"outerouter");
+    });
+}
+
+#[test]
+fn restore_without_redirect() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        assert!(jlrs.restore_stderr().is_ok());
+    });
+}
+
+fn print_string(julia: &mut Julia, s: &str) {
+    let code = format!("print(\"{}\")", s);
+    let printed = julia
+        .eval_string(
+            |global| Ok(Module::main(global)),
+            code,
+            |_, _, result| Ok(result.is_ok()),
+        )
+        .unwrap();
+    assert!(printed);
+}
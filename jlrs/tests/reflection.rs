@@ -0,0 +1,60 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn infer_return_types() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let main = Module::main(global);
+            frame
+                .eval_string(
+                    main,
+                    "stable(x) = 2x; unstable(x) = x > 0 ? x : 0.0; stable",
+                )?
+                .unwrap();
+
+            let int64 = Module::core(global).global("Int64")?;
+            let stable = main.function("stable")?;
+            let unstable = main.function("unstable")?;
+
+            let types = stable.return_types(&mut *frame, [int64])?;
+            assert_eq!(types.len(), 1);
+            assert!(types[0].egal(int64));
+
+            let ty = stable.return_type(&mut *frame, [int64])?;
+            assert!(ty.cast::<DataType>()?.is_concrete_type());
+
+            let ty = unstable.return_type(&mut *frame, [int64])?;
+            assert!(!ty.is::<DataType>());
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn show_generated_code() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(1, |global, frame| {
+            let float64 = Module::core(global).global("Float64")?;
+            let func = Module::base(global).function("sqrt")?;
+
+            let lowered = func.code_lowered(&mut *frame, [float64])?;
+            assert!(lowered.contains("CodeInfo"));
+            let typed = func.code_typed(&mut *frame, [float64])?;
+            assert!(typed.contains("Float64"));
+            let llvm = func.code_llvm(&mut *frame, [float64])?;
+            assert!(llvm.contains("define"));
+            let native = func.code_native(&mut *frame, [float64])?;
+            assert!(!native.is_empty());
+
+            assert_eq!(frame.size(), 0);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
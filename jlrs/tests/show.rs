@@ -0,0 +1,73 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn render_values() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.frame(2, |global, frame| {
+            let s = Value::new(&mut *frame, "foo")?;
+            assert_eq!(s.repr_string(&mut *frame)?, "\"foo\"");
+            assert_eq!(s.display_string(&mut *frame)?, "\"foo\"");
+            assert_eq!(s.to_string(), "foo");
+
+            let x = Value::new(&mut *frame, 1.0f32)?;
+            assert_eq!(x.repr_string(&mut *frame)?, "1.0f0");
+            assert_eq!(x.show_string(&mut *frame, None, true)?, "1.0");
+            assert_eq!(format!("{:#}", x), "1.0f0");
+
+            let nothing = Value::nothing(&mut *frame);
+            assert_eq!(nothing.to_string(), "nothing");
+            assert!(Module::base(global)
+                .function("sin")?
+                .show_string(&mut *frame, Some("text/html"), false)
+                .is_ok());
+            assert_eq!(frame.size(), 2);
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn render_arrays() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let arr = frame
+                .eval_string(Module::main(global), "[1, 2, 3]")?
+                .unwrap();
+            assert_eq!(arr.repr_string(&mut *frame)?, "[1, 2, 3]");
+            assert_eq!(
+                arr.display_string(&mut *frame)?,
+                "3-element Array{Int64,1}:\n 1\n 2\n 3"
+            );
+            Ok(())
+        })
+        .unwrap();
+    });
+}
+
+#[test]
+fn render_exceptions() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        jlrs.dynamic_frame(|global, frame| {
+            let func = Module::base(global).function("error")?;
+            let msg = Value::new(&mut *frame, "something went wrong")?;
+            let exc = func.call1(&mut *frame, msg)?.unwrap_err();
+
+            assert_eq!(exc.error_string(&mut *frame)?, "something went wrong");
+            assert_eq!(exc.to_string(), "something went wrong");
+            assert_eq!(
+                exc.repr_string(&mut *frame)?,
+                "ErrorException(\"something went wrong\")"
+            );
+            Ok(())
+        })
+        .unwrap();
+    });
+}
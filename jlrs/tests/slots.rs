@@ -0,0 +1,79 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+
+#[test]
+fn reuse_slot_in_static_frame() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let out = jlrs.frame(3, |global, frame| {
+            let add = Module::base(global).function("+")?;
+            let mut acc = frame.reserve_slot()?;
+            let mut arg = frame.reserve_slot()?;
+            let mut tmp = frame.reserve_slot()?;
+
+            let one = Value::new(&mut *frame, 1u64);
+            assert!(one.is_err());
+
+            Value::new_output(frame, acc.output(), 0u64);
+            for i in 0..100u64 {
+                let x = acc.get().unwrap();
+                let i = Value::new_output(frame, arg.output(), i);
+                let y = add.with_output(tmp.output()).call2(frame, x, i).unwrap();
+                acc.set(frame, y);
+            }
+
+            assert_eq!(frame.size(), 3);
+            acc.get().unwrap().cast::<u64>()
+        });
+
+        assert_eq!(out.unwrap(), 4950);
+    });
+}
+
+#[test]
+fn reuse_slot_in_dynamic_frame() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let out = jlrs.dynamic_frame(|global, frame| {
+            let mul = Module::base(global).function("*")?;
+            let mut acc = frame.reserve_slot()?;
+            let mut tmp = frame.reserve_slot()?;
+            assert!(acc.get().is_none());
+
+            let two = Value::new(frame, 2u64)?;
+            acc.set(frame, two);
+            for _ in 0..10 {
+                let x = acc.get().unwrap();
+                let y = mul.with_output(tmp.output()).call2(frame, x, two).unwrap();
+                acc.set(frame, y);
+            }
+
+            assert_eq!(frame.size(), 3);
+            acc.get().unwrap().cast::<u64>()
+        });
+
+        assert_eq!(out.unwrap(), 2048);
+    });
+}
+
+#[test]
+fn reserve_slot_in_nested_frame() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+
+        let out = jlrs.frame(1, |_global, frame| {
+            let mut slot = frame.reserve_slot()?;
+            frame.frame(1, |nested| {
+                let v = Value::new(nested, 3.0f64)?;
+                slot.set(nested, v);
+                Ok(())
+            })?;
+
+            slot.get().unwrap().cast::<f64>()
+        });
+
+        assert_eq!(out.unwrap(), 3.0);
+    });
+}
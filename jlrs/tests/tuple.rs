@@ -45,3 +45,51 @@ fn create_cast_tuple2() {
         .unwrap();
     })
 }
+
+#[test]
+fn create_cast_std_tuple() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.frame(1, |_global, frame| {
+            let v = Value::new(frame, (1i64, String::from("foo"), 2.5f64))?;
+            assert!(v.is::<Tuple>());
+            assert_eq!(v.n_fields(), 3);
+
+            let (a, b, c) = v.cast::<(i64, String, f64)>()?;
+            assert_eq!(a, 1);
+            assert_eq!(b, "foo");
+            assert_eq!(c, 2.5);
+
+            assert!(v.cast::<(i64, String)>().is_err());
+            assert!(v.cast::<(i64, f32, f64)>().is_err());
+            Ok(())
+        })
+        .unwrap();
+    })
+}
+
+#[test]
+fn cast_std_tuple_with_values() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        jlrs.frame(3, |global, frame| {
+            let v = frame
+                .eval_string(Module::main(global), "(1, \"foo\", [1.0, 2.0])")?
+                .unwrap();
+
+            let (a, b, c) = v.cast::<(i64, Value, Value)>()?;
+            assert_eq!(a, 1);
+            assert_eq!(b.cast::<String>()?, "foo");
+            assert!(c.is::<Array>());
+
+            let (a, b, c) = unsafe { v.cast_unchecked::<(i64, Value, Option<Value>)>() };
+            assert_eq!(a, 1);
+            assert_eq!(b.cast::<String>()?, "foo");
+            assert!(c.unwrap().is::<Array>());
+
+            assert!(v.cast::<(Value, Value, Value)>().is_err());
+            Ok(())
+        })
+        .unwrap();
+    })
+}
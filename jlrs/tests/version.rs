@@ -0,0 +1,58 @@
+use jlrs::prelude::*;
+use jlrs::util::JULIA;
+use jlrs::version::JuliaVersion;
+
+#[test]
+fn version_matches_julia() {
+    JULIA.with(|j| {
+        let mut jlrs = j.borrow_mut();
+        let version = jlrs.version();
+        assert!(version.is_supported());
+
+        let jl_version = jlrs
+            .frame(2, |global, frame| {
+                let version = Module::base(global).global("VERSION")?;
+                let string = Module::base(global)
+                    .function("string")?
+                    .call1(frame, version)?
+                    .unwrap();
+                string.cast::<String>()
+            })
+            .unwrap();
+
+        assert!(jl_version.starts_with(&format!(
+            "{}.{}.{}",
+            version.major, version.minor, version.patch
+        )));
+    });
+}
+
+#[test]
+fn version_available_before_init() {
+    let version = JuliaVersion::runtime();
+    assert_eq!((version.major, version.minor), JuliaVersion::SUPPORTED);
+}
+
+#[test]
+fn incompatible_version_error() {
+    let (major, minor) = JuliaVersion::SUPPORTED;
+    let version = JuliaVersion {
+        major,
+        minor: minor + 1,
+        patch: 5,
+        is_release: true,
+    };
+
+    assert!(!version.is_supported());
+    assert_eq!(version.to_string(), format!("{}.{}.5", major, minor + 1));
+    assert_eq!(
+        JlrsError::IncompatibleVersion(version).to_string(),
+        format!(
+            "Julia {}.{}.5 is not supported, jlrs requires Julia {}.{}",
+            major,
+            minor + 1,
+            major,
+            minor
+        )
+    );
+}
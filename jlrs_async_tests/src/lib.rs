@@ -41,7 +41,149 @@ mod example {
     #[cfg(test)]
     mod tests {
         use super::*;
+        use jlrs::interrupt::Interrupt;
+        use std::thread;
+        use std::time::{Duration, Instant};
+
+        type Runtime = AsyncJulia<f64, Sender<JlrsResult<f64>>>;
+
+        // Julia can only be initialized once per process, so these checks share the runtime
+        // that is started by `it_works`.
+        fn eval_string_works(julia: &Runtime) {
+            let res = julia
+                .try_eval_string(
+                    |global| Ok(Module::main(global)),
+                    "x = 1 + 2\nx * 2",
+                    |_, _, result| result.unwrap().cast::<i64>(),
+                )
+                .unwrap();
+            assert_eq!(res, 6);
+
+            let exception = async_std::task::block_on(julia.include_string(
+                |global| Ok(Module::main(global)),
+                "throw(ArgumentError(\"x\"))",
+                "throws.jl",
+                |_, frame, result| {
+                    let error = result.unwrap_err().get_field(frame, "error")?;
+                    Ok(error.type_name().to_string())
+                },
+            ))
+            .unwrap();
+            assert_eq!(exception, "ArgumentError");
+
+            let module = async_std::task::block_on(julia.eval_string(
+                |global| Module::main(global).submodule("MyModule"),
+                "nameof(@__MODULE__)",
+                |_, _, result| result.unwrap().cast::<Symbol>().map(Into::<String>::into),
+            ))
+            .unwrap();
+            assert_eq!(module, "MyModule");
+        }
+
+        fn capture_output_works(julia: &Runtime) {
+            let (res, output) = julia
+                .try_capture_output(|global, frame| {
+                    let msg = Value::new(frame, "captured")?;
+                    Module::base(global)
+                        .function("println")?
+                        .call1(frame, msg)?
+                        .unwrap();
+                    Ok(1)
+                })
+                .unwrap();
+            assert_eq!(res, 1);
+            assert_eq!(output, "captured\n");
+
+            let (_, output) = async_std::task::block_on(julia.capture_output(|global, frame| {
+                frame
+                    .eval_string(Module::main(global), "println(stderr, \"warning\")")?
+                    .unwrap();
+                Ok(())
+            }))
+            .unwrap();
+            assert_eq!(output, "warning\n");
+        }
         
+        // Drops the future returned by `call_async_interruptible` before the call has completed
+        // and drops the `Interrupt`, the Julia task still owns the flag and hits its timeout.
+        struct DroppedCall {
+            sender: Sender<JlrsResult<f64>>,
+        }
+
+        #[async_trait(?Send)]
+        impl JuliaTask for DroppedCall {
+            type T = f64;
+            type R = Sender<JlrsResult<Self::T>>;
+
+            async fn run<'base>(
+                &mut self,
+                global: Global<'base>,
+                frame: &mut AsyncFrame<'base>,
+            ) -> JlrsResult<Self::T> {
+                let secs = Value::new(frame, 30.0f64)?;
+                let sleeper = Module::main(global).function("jlrs_sleeper")?;
+                let interrupt = Interrupt::new();
+
+                let call = sleeper.call_async_interruptible(
+                    frame,
+                    [secs],
+                    &interrupt,
+                    Some(Duration::from_millis(200)),
+                );
+                assert!(async_std::future::timeout(Duration::from_millis(10), call)
+                    .await
+                    .is_err());
+
+                Ok(0.0)
+            }
+
+            fn return_channel(&self) -> Option<&Sender<JlrsResult<Self::T>>> {
+                Some(&self.sender)
+            }
+        }
+
+        fn dropped_interruptible_call_times_out(julia: &Runtime) {
+            julia
+                .try_eval_string(
+                    |global| Ok(Module::main(global)),
+                    "jlrs_sleeper_done = false
+                    function jlrs_sleeper(secs)
+                        try
+                            sleep(secs)
+                        finally
+                            global jlrs_sleeper_done = true
+                        end
+                    end",
+                    |_, _, result| {
+                        result.unwrap();
+                        Ok(())
+                    },
+                )
+                .unwrap();
+
+            let (sender, receiver) = crossbeam_channel::bounded(1);
+            julia.try_new_task(DroppedCall { sender }).unwrap();
+            assert_eq!(receiver.recv().unwrap().unwrap(), 0.0);
+
+            let start = Instant::now();
+            loop {
+                let done = julia
+                    .try_eval_string(
+                        |global| Ok(Module::main(global)),
+                        "jlrs_sleeper_done",
+                        |_, _, result| result.unwrap().cast::<bool>(),
+                    )
+                    .unwrap();
+
+                if done {
+                    break;
+                }
+
+                assert!(start.elapsed() < Duration::from_secs(10));
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+
         #[test]
         fn it_works() {
             let (julia, handle) =
@@ -70,6 +212,10 @@ mod example {
             
             assert_eq!(receiver2.recv().unwrap().unwrap(), 30_000_006.0);
             assert_eq!(receiver1.recv().unwrap().unwrap(), 20_000_004.0);
+
+            eval_string_works(&julia);
+            capture_output_works(&julia);
+            dropped_interruptible_call_times_out(&julia);
             
             std::mem::drop(julia);
             handle